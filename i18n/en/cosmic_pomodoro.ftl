@@ -1,15 +1,233 @@
 app-title = COSMIC Pomodoro
 app-version = Version
 about = About
+add-timer = New timer
+after-focus = Focus period is over.
 after-relax = Break is over..
+bank-and-break = Bank the rest and take a break
+banked = Banked for the next focus period: { $time }
 before-focus = Start focus timer!
 before-relax = Take a break!
+break-activities = Break activities
+    .placeholder = e.g. Stretch
+    .empty = Add activities to get one suggested at the start of each break.
+    .add = Add activity
+    .delete = Delete
+    .suggestion = How about: { $activity }
+break-now = Take a break now
+break-overlay = Break overlay
+    .off = Off
+    .flexible = Cover the screen, breaks can be skipped
+    .strict = Cover the screen until the break is over
+    .dim = Dim the screen a little
+clock = It is { $time }
+close-timer = Close timer
+countdown-in-title = Show the remaining time in the window title, so the taskbar shows it while the window is minimized
+countdown-restarted = The countdown had stopped and was started again, the time in between was not counted
+cycle-complete = ✓ Cycle complete, well done!
+cycle-finishes-at = Cycle finishes at { $time }
+distraction-log = Distractions today
+    .empty = No interruptions today
+    .entry = { $time }: { $note }
+    .no-note = no note
+display-granularity = Remaining time
+    .seconds = Minutes and seconds
+    .minutes = Whole minutes only
+export-history = Export history since launch
+fit-window-to-content = Fit the window to its content, until it is resized by hand
+focus-intensity = Focus intensity
+    .light = Light (15/5)
+    .standard = Standard (25/5)
+    .deep = Deep (50/10)
+focus-locked = Locked until the focus period is over
 focus-running = Focus on your tasks!
+high-contrast = High contrast for the progress ring and the remaining time, follows the system until changed
+hint = Getting started
+    .title = How it works
+    .phases = Press play to start a focus period. When it ends, take a break, then start the next focus period. After the last interval the cycle starts over.
+    .settings = Sounds, intervals and more can be changed under Menu → Settings.
+    .dismiss = Got it
+idle-prompt = Are you still there?
+    .body = The timer is waiting for the next phase to be started.
+interrupt = Interrupted ({ $count })
+    .placeholder = What pulled you away? (optional)
+intervals = Intervals
+    .focus = Focus (seconds)
+    .relax = Break (seconds)
+    .default-sound = Sound of the settings
+    .add = Add interval
+    .delete = Delete
+    .deleted = Interval deleted
+    .undo = Undo
+    .out-of-range = A length is { $min } to { $max } seconds (8 hours), longer ones are cut down and zero is not used
+key-bindings = Key bindings
+    .start-pause = Start / pause
+    .skip = Skip phase
+    .reset = Reset timer
+    .capturing = Press a key…
+    .unbound = Not set
+    .conflict = { $key } is already used by another action
+layout = Layout
+    .automatic = Depending on the window shape
+    .vertical = Vertical
+    .horizontal = Horizontal
+long-break-in = Long break in { $time }
+long-break-trigger = Long breaks
+    .intervals = As set for each interval
+    .focus-time = After a total focus time
 menu = Menu
+micro-reminder = Time for a sip of water and a stretch
+next-focus = Keep going, the next focus period has started!
+next-up = Next up: focus for { $focus }
+    .goal = Next up: focus for { $focus }, { $completed } of { $goal } done today
+note = Note
+    .placeholder = What do you want to focus on?
+notification-mode = Notifications
+    .sound-only = Sound only
+    .visual-only = Visual only
+    .both = Sound and visual
+    .none = None
+notification-progress = Show the focus periods done in the cycle in notifications
+    .summary = { $summary } ({ $completed }/{ $total })
+one-more = One more pomodoro
+one-more-waiting = { $count ->
+        [one] One more pomodoro to go after this interval
+       *[other] { $count } more pomodoros to go after this interval
+    }
+overtime-started = Focus period is over, counting the overtime until you move on.
+pause = Pause
+presentation-mode = Presentation mode
+    .active = Presentation mode: notifications are muted
+progress-style = Progress
+    .ring = Ring
+    .bar = Bar
+    .pie = Pie
+    .none = Only the remaining time
+ready-notifications = Notify when the next phase is ready to be started
+refresh-interval = Redraw a running timer every … milliseconds (16 to 1000, lower is smoother, higher saves battery)
 relax-running = Enjoy!
+remaining-minutes = { $minutes } min
+reset = Reset
+reset-daily = Start the cycle over every day, once the timers are stopped after midnight
+reset-dialog = Reset the timer?
+    .body = The timer starts over with the first interval. { $completed ->
+        [0] The running focus period is lost.
+        [one] The progress of { $completed } completed focus period in this cycle is lost.
+       *[other] The progress of { $completed } completed focus periods in this cycle is lost.
+    }
+    .confirm = Reset
+    .cancel = Cancel
+restore-paused = Restore a running timer paused on the next launch
+restore-position = On the next launch, only go back to the interval you were at, ready to start
+run-in-background = Keep the timers running when the window is closed, launch again to show it (after a restart)
+schedule = Schedule
+schedule-entry = { $index }. Focus { $focus } / Break { $relax }
+    .focus-only = { $index }. Focus { $focus }
+session-time = Session time: { $time }
+    .wall-clock = Count the session time on the clock, also while all timers are paused or stopped
+shortcut-hints = Show the keyboard shortcuts below the buttons
+skip = Skip
+skip-behavior = Skip behavior
+    .discard = Discard the remaining time
+    .carry-over = Add the remaining time to the next phase
+start = Start
+start-phase = Start phase
+    .focus = A focus period
+    .relax = A break
+statistics = Statistics
+    .completed-today = Completed today: { $count }
+    .interruptions-today = Interruptions today: { $count }
+    .overtime-today = Overtime today: { $time }
+    .daily-goal = Daily goal (0 to turn off streaks)
+    .count-partial = Count the time of interrupted focus periods (off by default)
+    .count-after-break = Count a pomodoro as completed once its break is over, instead of when the focus period ends
+    .current-streak = Current streak: { $days ->
+        [one] { $days } day
+       *[other] { $days } days
+    }
+    .longest-streak = Longest streak: { $days ->
+        [one] { $days } day
+       *[other] { $days } days
+    }
+summary = Summary of the day
+    .at-time = Send the summary at a set time
+    .time = Send it at
+    .on-goal = Send the summary when the daily goal is reached
+    .notification = You completed { $count ->
+        [one] { $count } pomodoro
+       *[other] { $count } pomodoros
+    } and focused for { $hours }h { $minutes }m today.
+summary-card = Share today as an image
+    .title = Today's focus
+    .completed = { $count ->
+        [one] pomodoro
+       *[other] pomodoros
+    }
+    .focus = focused
+    .focus-time = { $hours }h { $minutes }m
+    .streak = { $days ->
+        [one] day streak
+       *[other] days streak
+    }
+    .empty = Nothing to share yet today
+timeline = Planned cycle
+timer-tab = Timer { $index }
+transient-notifications = Transient notifications
+    .break = Don't keep the notification that a break starts in the notification list (on by default)
+    .focus = Don't keep the notification that a focus period starts in the notification list
+window-title = { $phase } – { app-title }
+    .countdown = { $remaining } · { $phase } – { app-title }
 welcome = Welcome to COSMIC!
+write-state-file = Write the state of the timer to a file for status bars, in $XDG_RUNTIME_DIR/cosmic-pomodoro/state.json
 
 settings = Settings
+    .search-placeholder = Search settings
+    .sound-filter = Filter sounds
+    .sound-filter-placeholder = Type to filter, e.g. alarm
+    .seconds-placeholder = Seconds
     .end-of-focus-sound = Sound at the end of a focus period
     .end-of-relax-sound = Sound relax period
+    .sound-repeat = Play the sound { $count ->
+        [one] once
+       *[other] { $count } times
+    }
+    .auto-start-break-when-focused = Start the break right away if the window is focused (on by default)
+    .overtime = Count into overtime if the break doesn't start on its own, until you skip to it
+    .pause-on-unfocus = Pause focus periods while the window is not focused
+    .unfocus-grace = Pause only after the window was not focused for … seconds
+    .focus-lock = Lock running focus periods, they can't be paused, skipped or reset until they're over
+    .purist = Pomodoro technique purist
+    .purist-explanation = Four pomodoros of 25 minutes with 5 minute breaks, then a 15 minute break. Breaks can't be skipped and an interruption voids the pomodoro, which starts over. The intervals, the long break and the options against the rules are fixed until this is turned off.
+    .off = Off
+    .adjust-keeps-interval = Keep lengths changed with the up and down arrow keys for the next cycles
+    .skip-behavior = When skipping a phase, its remaining time is
+    .notification-mode = At the end of a phase, notify with
+    .warning-threshold = Highlight the last seconds of a focus period (0 to turn off)
+    .micro-reminder-interval = Remind to drink and stretch during a focus period every … seconds (0 to turn off)
+    .idle-prompt = Ask if you are still there while waiting to start a phase, every … seconds (0 to turn off)
+    .show-clock = Show the current time
+    .layout = Layout
+    .display-granularity = Show the remaining time as
+    .final-countdown = Count down the last minute of a focus period in big seconds
+    .lunch-break = Long break during lunch time
+    .lunch-start = Lunch starts at
+    .lunch-end = Lunch ends at
+    .lunch-break-length = Lunch break length (seconds)
+    .quiet-hours = Quiet hours without sounds
+    .quiet-start = Quiet hours start at
+    .quiet-end = Quiet hours end at
+    .quiet-pause = Pause phases that start on their own during quiet hours
+    .quiet-notifications = Still show notifications during quiet hours
+    .long-break-trigger = Take a long break
+    .long-break-focus = Total focus time before a long break (minutes)
+    .start-phase = A new or reset timer starts with
+    .continuous-focus = No breaks, go straight to the next focus period
+    .break-overlay = During breaks
+    .dim-opacity = How much to dim the screen
+    .progress-style = Show the progress as
+    .ring-stroke-width = Progress ring thickness
+    .ring-size = Progress ring size
+    .ease-ring = Slow the progress ring down towards the end of a phase
+    .celebrate-cycle = Celebrate once all intervals are done
+    .pulse-when-paused = Let the progress ring breathe while paused (uses more battery)
 
@@ -1,28 +1,106 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::core::config::Config;
+#[cfg(feature = "control-socket")]
+use crate::core::control_socket::{self, ControlCommand};
+#[cfg(feature = "http-status")]
+use crate::core::http_status;
+#[cfg(any(feature = "control-socket", feature = "http-status"))]
+use crate::core::reply::Reply;
+use crate::core::do_not_disturb;
 use crate::core::duration_extension::TimeDurationExt;
+use crate::core::duration_string;
 use crate::core::icon_cache;
-use crate::core::pomodoro_timer::{PomodoroPhase, PomodoroState, PomodoroTimer};
+use crate::core::localization;
+use crate::core::session_history;
+use crate::core::sound;
+use crate::core::state_file;
+use crate::core::summary_card::SummaryCard;
+use crate::core::transition_hook::{self, TransitionHook};
+use crate::core::work_session::WorkSession;
+use crate::core::key_bindings::{BindableAction, KeyBindings, StoredKeyBind};
+use crate::core::statistics;
+use crate::core::pomodoro_timer::{PomodoroLength, PomodoroPhase, PomodoroState, PomodoroTimer, TimerEvent, TimerSnapshot};
 use crate::fl;
-use crate::views::settings::SettingMessage;
-use cosmic::app::{Command, Core};
+use crate::views::settings::{DisplayGranularity, Layout, NotificationMode, ProgressStyle, SettingMessage, Settings, TIME_FORMAT};
+#[cfg(feature = "wayland")]
+use crate::views::settings::BreakOverlay;
+#[cfg(feature = "wayland")]
+use crate::views::break_overlay::{get_break_overlay_view, get_dim_view};
+use crate::views::break_activities::get_break_activities_view;
+use crate::views::key_bindings::get_key_bindings_view;
+use crate::views::statistics::{get_distraction_log_view, get_statistics_view};
+use crate::views::timeline::{get_timeline_view, phase_color};
+use chrono::{Local, NaiveDate, NaiveTime};
+use cosmic::app::{Command, Core, CosmicFlags, DbusActivationMessage};
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
 use cosmic::iced::alignment::{Horizontal, Vertical};
-use cosmic::iced::time;
-use cosmic::iced::{Alignment, ContentFit, Length, Subscription};
+use cosmic::iced::keyboard::{self, Key, Modifiers};
+use cosmic::iced::futures::SinkExt;
+use cosmic::iced::{event, subscription, time};
+use cosmic::iced::widget::container;
+use cosmic::iced::{Alignment, Background, Color, ContentFit, Length, Size, Subscription};
+use cosmic::iced::window;
+#[cfg(feature = "wayland")]
+use cosmic::iced::Limits;
+#[cfg(feature = "wayland")]
+use cosmic::iced::wayland::actions::layer_surface::{IcedOutput, SctkLayerSurfaceSettings};
+#[cfg(feature = "wayland")]
+use cosmic::iced_sctk::commands::layer_surface::{destroy_layer_surface, get_layer_surface, Anchor, KeyboardInteractivity, Layer};
+use cosmic::widget::menu::key_bind::KeyBind;
 use cosmic::widget::{self, menu};
 use cosmic::{cosmic_theme, iced_widget, theme, Application, ApplicationExt, Apply, Element};
-use notify_rust::Notification;
+use notify_rust::{Hint, Notification, NotificationHandle, Urgency};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
 use quick_xml::Writer;
+use std::any::TypeId;
 use std::collections::HashMap;
 use std::io::Cursor;
+use std::ops::RangeInclusive;
 use std::str;
 use std::sync::atomic::Ordering;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const REPOSITORY: &str = "https://github.com/Spoomer/cosmic-pomodoro";
 const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// How long a deleted interval can be restored.
+const UNDO_DELETE_WINDOW: Duration = Duration::from_secs(5);
+/// How long the window background flashes on a phase transition.
+const TRANSITION_FLASH_DURATION: Duration = Duration::from_secs(1);
+/// How long the completion of a cycle is celebrated.
+const CELEBRATION_DURATION: Duration = Duration::from_secs(3);
+/// Micro reminders aren't sent this close to the end of a focus period.
+const MICRO_REMINDER_QUIET_SECS: u32 = 60;
+/// How long a notification that couldn't be shown stays in the window instead.
+const NOTIFICATION_BANNER_DURATION: Duration = Duration::from_secs(10);
+/// How often the state file is written at most while the time goes by.
+const STATE_FILE_INTERVAL: Duration = Duration::from_secs(1);
+/// Window sizes fitting the main view in the vertical and horizontal layout.
+const FITTED_VERTICAL_SIZE: (u32, u32) = (360, 600);
+const FITTED_HORIZONTAL_SIZE: (u32, u32) = (640, 360);
+/// What an open context drawer adds to the fitted window, and how high it needs the window to be.
+const FITTED_DRAWER_WIDTH: u32 = 480;
+const FITTED_DRAWER_HEIGHT: u32 = 768;
+/// Limits of `Config::refresh_interval_ms`, from about 60 redraws a second to one.
+const REFRESH_INTERVAL_RANGE: RangeInclusive<u32> = 16..=1000;
+/// Radius of the progress ring in the icons at a ring size of 100%.
+const RING_RADIUS: f32 = 250.0;
+/// The ring color in high contrast, the icons draw it on a dark circle in any theme.
+const HIGH_CONTRAST_RING_COLOR: &str = "rgb(255, 255, 255)";
+/// How much thicker the ring is drawn in high contrast.
+const HIGH_CONTRAST_STROKE_FACTOR: f32 = 2.0;
+/// The canvas of the bundled icons, the progress ring is drawn for its center at 260, 260.
+const BUNDLED_VIEW_BOX: &str = "0 0 520 520";
+/// The filling of [`ProgressStyle::Pie`], see-through so the icon stays readable.
+const PIE_FILL_COLOR: &str = "rgba(209, 209, 209, 0.35)";
+/// One breath of the ring of a paused timer, and the redraws it needs to look smooth enough.
+const PAUSE_PULSE_PERIOD: Duration = Duration::from_secs(3);
+const PAUSE_PULSE_REFRESH: Duration = Duration::from_millis(150);
+/// Seconds of the last minute of a focus period, shown as one big number, see [`Settings::final_countdown`].
+const FINAL_COUNTDOWN_SECS: u32 = 60;
+/// How much the arrow keys change the length of the phase waiting to be started.
+const ADJUST_STEP_SECS: i32 = 60;
 
 /// This is the struct that represents your application.
 /// It is used to define the data that will be used by your application.
@@ -33,9 +111,74 @@ pub struct CosmicPomodoro {
     context_page: ContextPage,
     /// Key bindings for the application's menu bar.
     key_binds: HashMap<menu::KeyBind, MenuAction>,
-    pomodoro_timer: PomodoroTimer,
+    /// Writes changes of `config` back, if the config could be opened.
+    config_handler: Option<cosmic_config::Config>,
+    config: Config,
+    /// Independent timers, shown as tabs when there is more than one.
+    timers: Vec<PomodoroTimer>,
+    /// The timer shown in the main view and the settings.
+    active_timer: usize,
+    /// Whether the dialog confirming a reset is shown.
+    confirming_reset: bool,
+    /// The last interval removed in the settings, kept for a short time to allow undo.
+    deleted_interval: Option<DeletedInterval>,
+    /// When the last phase transition happened, while its flash is still visible.
+    transition_flash: Option<Instant>,
+    /// When the active timer last completed all its intervals, while that is still celebrated.
+    celebration: Option<Instant>,
+    /// Suppresses all notifications, e.g. while sharing the screen.
+    presentation_mode: bool,
+    /// `config.run_in_background` at launch, when it was applied to the window settings, or a headless launch.
+    run_in_background: bool,
+    /// The last notification about a phase change, replaced by the next one.
+    phase_notification: Option<NotificationHandle>,
+    /// The last notification that couldn't be shown, e.g. without a notification daemon, and since when it's shown in the window.
+    notification_banner: Option<(String, Instant)>,
+    /// Width and height of the main window, once it is known.
+    window_size: Option<(u32, u32)>,
+    /// The last size the window was fitted to.
+    fitted_window_size: Option<(u32, u32)>,
+    /// The window was resized by hand, so it's no longer fitted to the view.
+    manually_resized: bool,
+    /// Ids of the timers paused because the window lost focus, resumed once it is focused again.
+    paused_on_unfocus: Vec<usize>,
+    /// When the window lost focus, `None` while it's focused, for the grace period of pausing on unfocus.
+    unfocused_since: Option<Instant>,
+    /// The last title set, to only update the window title when it changes.
+    window_title: String,
+    summary_time_input: String,
+    /// The action whose key binding is replaced by the next key press.
+    capturing_key_bind: Option<BindableAction>,
+    /// Why the last captured key binding was rejected.
+    key_bind_conflict: Option<String>,
+    /// Platform specific cues on phase transitions, e.g. an LED blink.
+    transition_hooks: Vec<Box<dyn TransitionHook>>,
+    /// When the state file was last written.
+    state_file_written: Option<Instant>,
+    /// Today's work session, once a timer was started today.
+    work_session: Option<WorkSession>,
+    /// The layer surface covering the screen during a break, while it is shown.
+    #[cfg(feature = "wayland")]
+    break_overlay: Option<window::Id>,
 }
 
+/// Flags for the single instance, a second launch only shows the window of the running one.
+#[derive(Clone, Debug, Default)]
+pub struct Flags {
+    /// Starts without a window, only notifying, until a second launch shows it. Set with `--headless`.
+    pub headless: bool,
+}
+
+impl CosmicFlags for Flags {
+    type SubCommand = String;
+    type Args = Vec<String>;
+}
+
+struct DeletedInterval {
+    index: usize,
+    length: PomodoroLength,
+    deleted_at: Instant,
+}
 
 /// This is the enum that contains all the possible variants that your application will need to transmit messages.
 /// This is used to communicate between the different parts of your application.
@@ -45,8 +188,83 @@ pub enum Message {
     LaunchUrl(String),
     ToggleContextPage(ContextPage),
     StartTimer,
+    SkipPhase,
+    /// Resets the active timer, after asking if progress would be lost.
+    RequestReset,
+    ConfirmReset,
+    CancelReset,
     Refresh,
+    /// The countdown of the timer with the given id reached zero.
+    TimerElapsed(usize),
     ChangeSetting(SettingMessage),
+    ExportHistory,
+    HistoryExported(Result<(), String>),
+    /// Saves a card of today's statistics as an image, see [`SummaryCard`].
+    ExportSummaryCard,
+    SummaryCardExported(Result<(), String>),
+    AddTimer,
+    SelectTimer(usize),
+    CloseTimer(usize),
+    DismissHint,
+    Key(Modifiers, Key),
+    CaptureKeyBind(BindableAction),
+    WindowResized(u32, u32),
+    WindowFocusChanged(bool),
+    /// Pauses the timers whose grace period ran out while the window is unfocused, see `Settings::unfocus_grace_secs`.
+    PauseUnfocused,
+    /// Starts an ad-hoc focus period of the given seconds.
+    QuickStart(u32),
+    NoteChanged(String),
+    /// Counts an interruption of the running focus period, logged with the distraction note.
+    Interrupt,
+    DistractionNoteChanged(String),
+    /// A command from the control socket, answered through the reply.
+    #[cfg(feature = "control-socket")]
+    Control(ControlCommand, Reply),
+    /// A request of the status server, answered with the state of the active timer.
+    #[cfg(feature = "http-status")]
+    HttpStatus(Reply),
+    /// Banks the rest of the focus period and takes the break now, see [`PomodoroTimer::bank_and_break`].
+    BankAndBreak,
+    /// Takes a break right away from any phase, see [`PomodoroTimer::break_now`].
+    BreakNow,
+    /// Changes the length of the phase waiting to be started by the given seconds, see [`PomodoroTimer::adjust_upcoming_length`].
+    AdjustUpcomingLength(i32),
+    /// Adds one more pomodoro to the sequence just this once, see [`PomodoroTimer::add_one_off`].
+    AddOneOff,
+    DailyGoalChanged(String),
+    SummaryAtTimeChanged(bool),
+    SummaryTimeChanged(String),
+    SummaryOnGoalChanged(bool),
+    StatsCountPartialChanged(bool),
+    CountAfterBreakChanged(bool),
+    RestorePausedChanged(bool),
+    RestorePositionOnlyChanged(bool),
+    HighContrastChanged(bool),
+    ShortcutHintsChanged(bool),
+    CountdownInTitleChanged(bool),
+    TransientBreakNotificationsChanged(bool),
+    TransientFocusNotificationsChanged(bool),
+    SessionTimeWallClockChanged(bool),
+    WriteStateFileChanged(bool),
+    FitWindowToContentChanged(bool),
+    ReadyNotificationsChanged(bool),
+    NotificationProgressChanged(bool),
+    RefreshIntervalChanged(String),
+    BreakActivityChanged(usize, String),
+    AddBreakActivity,
+    DeleteBreakActivity(usize),
+    /// Sends the summary of the day if its time has come.
+    CheckSummary,
+    /// Prompts if a timer waits for the next phase for too long.
+    CheckIdle,
+    /// Starts the cycle over if the day changed, see `Config::reset_daily`.
+    CheckDay,
+    ResetDailyChanged(bool),
+    TogglePresentationMode,
+    RunInBackgroundChanged(bool),
+    /// The main window is closing, so the app either quits or keeps running without a window.
+    MainWindowClosed,
 }
 
 /// Identifies a context page to display in the context drawer.
@@ -55,6 +273,8 @@ pub enum ContextPage {
     #[default]
     About,
     Settings,
+    Hint,
+    Statistics,
 }
 
 impl ContextPage {
@@ -62,6 +282,8 @@ impl ContextPage {
         match self {
             Self::About => fl!("about"),
             Self::Settings => fl!("settings"),
+            Self::Hint => fl!("hint"),
+            Self::Statistics => fl!("statistics"),
         }
     }
 }
@@ -70,6 +292,17 @@ impl ContextPage {
 pub enum MenuAction {
     About,
     Settings,
+    Statistics,
+    ExportHistory,
+    AddTimer,
+    PresentationMode,
+    QuickStart(u32),
+    AdjustUpcomingLength(i32),
+    AddOneOff,
+    BreakNow,
+    StartTimer,
+    SkipPhase,
+    ResetTimer,
 }
 
 impl menu::action::MenuAction for MenuAction {
@@ -79,6 +312,17 @@ impl menu::action::MenuAction for MenuAction {
         match self {
             MenuAction::About => Message::ToggleContextPage(ContextPage::About),
             MenuAction::Settings => { Message::ToggleContextPage(ContextPage::Settings) }
+            MenuAction::Statistics => Message::ToggleContextPage(ContextPage::Statistics),
+            MenuAction::ExportHistory => Message::ExportHistory,
+            MenuAction::AddTimer => Message::AddTimer,
+            MenuAction::PresentationMode => Message::TogglePresentationMode,
+            MenuAction::QuickStart(secs) => Message::QuickStart(*secs),
+            MenuAction::AdjustUpcomingLength(delta_secs) => Message::AdjustUpcomingLength(*delta_secs),
+            MenuAction::AddOneOff => Message::AddOneOff,
+            MenuAction::BreakNow => Message::BreakNow,
+            MenuAction::StartTimer => Message::StartTimer,
+            MenuAction::SkipPhase => Message::SkipPhase,
+            MenuAction::ResetTimer => Message::RequestReset,
         }
     }
 }
@@ -94,7 +338,7 @@ impl menu::action::MenuAction for MenuAction {
 impl Application for CosmicPomodoro {
     type Executor = cosmic::executor::Default;
 
-    type Flags = ();
+    type Flags = Flags;
 
     type Message = Message;
 
@@ -115,19 +359,85 @@ impl Application for CosmicPomodoro {
     /// - `core` is used to passed on for you by libcosmic to use in the core of your own application.
     /// - `flags` is used to pass in any data that your application needs to use before it starts.
     /// - `Command` type is used to send messages to your application. `Command::none()` can be used to send no messages to your application.
-    fn init(core: Core, _flags: Self::Flags) -> (Self, Command<Self::Message>) {
+    fn init(core: Core, flags: Self::Flags) -> (Self, Command<Self::Message>) {
+        let (config_handler, config) = Config::load(Self::APP_ID);
+        let summary_time_input = config.summary_at
+            .unwrap_or_else(default_summary_time)
+            .format(TIME_FORMAT)
+            .to_string();
+        let timers = restore_timers(&config.saved_timers, config.restore_paused, config.restore_position_only);
+        // without a window to begin with, closing it once it's shown mustn't end the timers
+        let run_in_background = config.run_in_background || flags.headless;
+        // phases that ended while the app was closed
+        let elapsed = timers.iter()
+            .filter(|timer| timer.pomodoro_state == PomodoroState::Run && timer.remaining_sec.load(Ordering::SeqCst) == 0)
+            .map(|timer| {
+                let id = timer.id;
+                Command::perform(async move { id }, |id| cosmic::app::message::app(Message::TimerElapsed(id)))
+            })
+            .collect::<Vec<_>>();
         let mut app = CosmicPomodoro {
             core,
             context_page: ContextPage::default(),
-            key_binds: HashMap::new(),
-            pomodoro_timer: PomodoroTimer::new(),
+            key_binds: key_binds(&config.key_bindings),
+            config_handler,
+            config,
+            timers,
+            active_timer: 0,
+            confirming_reset: false,
+            deleted_interval: None,
+            transition_flash: None,
+            celebration: None,
+            presentation_mode: false,
+            run_in_background,
+            phase_notification: None,
+            notification_banner: None,
+            window_size: None,
+            fitted_window_size: None,
+            manually_resized: false,
+            paused_on_unfocus: Vec::new(),
+            unfocused_since: None,
+            window_title: String::new(),
+            summary_time_input,
+            capturing_key_bind: None,
+            key_bind_conflict: None,
+            transition_hooks: transition_hook::registered_hooks(),
+            state_file_written: None,
+            work_session: None,
+            #[cfg(feature = "wayland")]
+            break_overlay: None,
         };
 
-        let command = app.update_titles();
+        if !app.config.hint_dismissed {
+            app.context_page = ContextPage::Hint;
+            app.core.window.show_context = true;
+            app.set_context_title(ContextPage::Hint.title());
+        }
+
+        // the timers may have been left the day before
+        app.reset_on_new_day();
+        let mut command = Command::batch(elapsed.into_iter().chain([app.update_titles()]));
+        if flags.headless {
+            command = Command::batch([command, window::change_mode(window::Id::MAIN, window::Mode::Hidden)]);
+        }
 
         (app, command)
     }
 
+    /// Asks before a reset loses the progress of the cycle.
+    fn dialog(&self) -> Option<Element<Self::Message>> {
+        if !self.confirming_reset {
+            return None;
+        }
+        let dialog = widget::dialog(fl!("reset-dialog"))
+            .body(fl!("reset-dialog", "body", completed = self.timer().completed_in_cycle()))
+            .primary_action(widget::button::destructive(fl!("reset-dialog", "confirm"))
+                .on_press(Message::ConfirmReset))
+            .secondary_action(widget::button::standard(fl!("reset-dialog", "cancel"))
+                .on_press(Message::CancelReset));
+        Some(dialog.into())
+    }
+
     /// Display a context drawer if the context page is requested.
     fn context_drawer(&self) -> Option<Element<Self::Message>> {
         if !self.core.window.show_context {
@@ -136,7 +446,115 @@ impl Application for CosmicPomodoro {
 
         Some(match self.context_page {
             ContextPage::About => self.about(),
-            ContextPage::Settings => self.pomodoro_timer.settings.get_settings_view()
+            ContextPage::Hint => self.hint(),
+            ContextPage::Statistics => widget::column()
+                .push(get_statistics_view(&self.config, &self.summary_time_input, Local::now().date_naive()))
+                .push(get_distraction_log_view(
+                    self.timer().history.records.iter()
+                        .flat_map(|record| &record.distractions)
+                        .chain(&self.timer().distractions),
+                    Local::now().date_naive(),
+                ))
+                .push(get_timeline_view(
+                    &self.timer().pomodoro_lengths,
+                    self.timer().position,
+                    self.timer().pomodoro_phase,
+                    self.timer().settings.continuous_focus,
+                ))
+                .spacing(10)
+                .into(),
+            ContextPage::Settings => widget::column()
+                .push(self.timer().settings.get_settings_view(
+                    &self.timer().pomodoro_lengths,
+                    self.deleted_interval.is_some(),
+                    self.config.notification_mode,
+                    phase_color(self.timer().pomodoro_phase),
+                ))
+                .push(get_break_activities_view(&self.config.break_activities, phase_color(self.timer().pomodoro_phase)))
+                .push(get_key_bindings_view(
+                    &self.config.key_bindings,
+                    self.capturing_key_bind,
+                    self.key_bind_conflict.as_deref(),
+                    phase_color(self.timer().pomodoro_phase),
+                ))
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("ready-notifications")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.ready_notifications, Message::ReadyNotificationsChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("notification-progress")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.notification_progress, Message::NotificationProgressChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("transient-notifications", "break")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.transient_break_notifications, Message::TransientBreakNotificationsChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("transient-notifications", "focus")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.transient_focus_notifications, Message::TransientFocusNotificationsChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("session-time", "wall-clock")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.session_time_wall_clock, Message::SessionTimeWallClockChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("write-state-file")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.write_state_file, Message::WriteStateFileChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("fit-window-to-content")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.fit_window_to_content, Message::FitWindowToContentChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("refresh-interval")).vertical_alignment(Vertical::Center))
+                    .push(widget::text_input("", self.config.refresh_interval_ms.to_string())
+                        .on_input(Message::RefreshIntervalChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("high-contrast")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.is_high_contrast(), Message::HighContrastChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("shortcut-hints")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.shortcut_hints, Message::ShortcutHintsChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("countdown-in-title")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.countdown_in_title, Message::CountdownInTitleChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("reset-daily")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.reset_daily, Message::ResetDailyChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("restore-paused")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.restore_paused, Message::RestorePausedChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("restore-position")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.restore_position_only, Message::RestorePositionOnlyChanged))
+                    .spacing(10)
+                )
+                .push(widget::row::with_capacity(2)
+                    .push(widget::text::text(fl!("run-in-background")).vertical_alignment(Vertical::Center))
+                    .push(widget::toggler(None, self.config.run_in_background, Message::RunInBackgroundChanged))
+                    .spacing(10)
+                )
+                .spacing(10)
+                .into(),
         })
     }
 
@@ -148,7 +566,13 @@ impl Application for CosmicPomodoro {
                 &self.key_binds,
                 vec![
                     menu::Item::Button(fl!("about"), MenuAction::About),
-                    menu::Item::Button(fl!("settings"), MenuAction::Settings)
+                    menu::Item::Button(fl!("settings"), MenuAction::Settings),
+                    menu::Item::Button(fl!("statistics"), MenuAction::Statistics),
+                    menu::Item::Button(fl!("export-history"), MenuAction::ExportHistory),
+                    menu::Item::Button(fl!("add-timer"), MenuAction::AddTimer),
+                    menu::Item::Button(fl!("one-more"), MenuAction::AddOneOff),
+                    menu::Item::Button(fl!("break-now"), MenuAction::BreakNow),
+                    menu::Item::CheckBox(fl!("presentation-mode"), self.presentation_mode, MenuAction::PresentationMode),
                 ],
             ),
         )]);
@@ -160,6 +584,22 @@ impl Application for CosmicPomodoro {
     /// what message was received. Commands may be returned for asynchronous execution on a
     /// background thread managed by the application's executor.
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+        // the time until now counts with the state before the message
+        let was_running = self.any_timer_running();
+        if let Some(session) = &mut self.work_session {
+            session.update(Instant::now(), was_running);
+        }
+        let changes_timers = matches!(message,
+            Message::StartTimer | Message::SkipPhase | Message::BankAndBreak | Message::BreakNow | Message::ConfirmReset | Message::TimerElapsed(_)
+            | Message::QuickStart(_) | Message::AddTimer | Message::CloseTimer(_) | Message::WindowFocusChanged(_)
+            | Message::PauseUnfocused | Message::AdjustUpcomingLength(_));
+        // a locked focus period can only run out, whichever way these come in
+        let interrupts_focus = matches!(message,
+            Message::StartTimer | Message::SkipPhase | Message::BankAndBreak | Message::BreakNow | Message::RequestReset
+            | Message::ConfirmReset | Message::QuickStart(_));
+        if interrupts_focus && self.timer().is_locked() || self.is_against_purist(&message) {
+            return Command::none();
+        }
         match message {
             Message::LaunchUrl(url) => {
                 let _result = open::that_detached(url);
@@ -179,75 +619,541 @@ impl Application for CosmicPomodoro {
                 self.set_context_title(context_page.title());
             }
             Message::StartTimer => {
-                match self.pomodoro_timer.pomodoro_state {
+                self.start_work_session();
+                let timer = self.timer_mut();
+                match timer.pomodoro_state {
                     PomodoroState::Stop => {
-                        self.pomodoro_timer.pomodoro_phase = match self.pomodoro_timer.pomodoro_phase {
+                        timer.pomodoro_phase = match timer.pomodoro_phase {
                             PomodoroPhase::BeforeFocus => PomodoroPhase::Focus,
+                            // the break was loaded before breaks were turned off
+                            PomodoroPhase::BeforeRelax if timer.settings.continuous_focus => {
+                                timer.enter_before_focus(false);
+                                PomodoroPhase::Focus
+                            }
                             PomodoroPhase::Focus => PomodoroPhase::BeforeRelax,
                             PomodoroPhase::BeforeRelax => PomodoroPhase::Relax,
                             PomodoroPhase::Relax => PomodoroPhase::BeforeFocus,
                         };
-                        self.pomodoro_timer.start()
+                        timer.start()
                     }
                     PomodoroState::Run => {
-                        self.pomodoro_timer.pause()
+                        timer.pause()
                     }
                     PomodoroState::Pause => {
-                        self.pomodoro_timer.resume()
+                        timer.resume()
+                    }
+                }
+                // a phase with no time left, e.g. resumed at zero, ends right away instead of running empty
+                if self.timer().has_run_out() {
+                    return self.update(Message::TimerElapsed(self.timer().id));
+                }
+            }
+            Message::SkipPhase => {
+                let recorded = self.timer().history.records.len();
+                self.timer_mut().skip();
+                self.add_focus_time(self.active_timer, recorded);
+            }
+            #[cfg(feature = "http-status")]
+            Message::HttpStatus(reply) => {
+                let timer = self.timer();
+                reply.send(state_file::to_json(
+                    timer.pomodoro_phase,
+                    timer.pomodoro_state,
+                    timer.remaining_sec.load(Ordering::SeqCst),
+                    timer.initial_sec,
+                ));
+            }
+            #[cfg(feature = "control-socket")]
+            Message::Control(command, reply) => {
+                let state = self.timer().pomodoro_state;
+                let message = match command {
+                    ControlCommand::Start => (state != PomodoroState::Run).then_some(Message::StartTimer),
+                    ControlCommand::Pause => (state == PomodoroState::Run).then_some(Message::StartTimer),
+                    ControlCommand::Skip => Some(Message::SkipPhase),
+                    // there's nobody to ask
+                    ControlCommand::Reset => Some(Message::ConfirmReset),
+                    ControlCommand::Status => {
+                        let timer = self.timer();
+                        reply.send(state_file::to_json(
+                            timer.pomodoro_phase,
+                            timer.pomodoro_state,
+                            timer.remaining_sec.load(Ordering::SeqCst),
+                            timer.initial_sec,
+                        ));
+                        None
+                    }
+                };
+                if message.is_some() && self.timer().is_locked() {
+                    reply.send("{\"error\":\"the focus period is locked\"}".to_string());
+                    return Command::none();
+                }
+                if message.as_ref().is_some_and(|message| self.is_against_purist(message)) {
+                    reply.send("{\"error\":\"the purist mode keeps the break\"}".to_string());
+                    return Command::none();
+                }
+                // the status was answered already, a reply is only sent once
+                reply.ok();
+                if let Some(message) = message {
+                    return self.update(message);
+                }
+            }
+            Message::AdjustUpcomingLength(delta_secs) => {
+                let keep = self.timer().settings.adjust_keeps_interval;
+                self.timer_mut().adjust_upcoming_length(delta_secs, keep);
+            }
+            Message::AddOneOff => {
+                self.timer_mut().add_one_off();
+            }
+            Message::BreakNow => {
+                let index = self.active_timer;
+                let recorded = self.timer().history.records.len();
+                // looked up before the break replaces the focus period, like at its end
+                let sound_name = self.timer().phase_end_sound().to_string();
+                self.start_work_session();
+                if let Some(left) = self.timer_mut().break_now() {
+                    self.add_focus_time(index, recorded);
+                    for hook in &self.transition_hooks {
+                        hook.on_transition(left, self.timers[index].pomodoro_phase);
+                    }
+                    // only an ended focus period is announced like at its end, a waiting break was just started
+                    if left == PomodoroPhase::Focus {
+                        self.notify_phase_end(index, left, &sound_name);
                     }
+                    self.transition_flash = Some(Instant::now());
+                }
+            }
+            Message::BankAndBreak => {
+                let recorded = self.timer().history.records.len();
+                if self.timer_mut().bank_and_break() {
+                    self.add_focus_time(self.active_timer, recorded);
+                }
+            }
+            Message::RequestReset => {
+                let timer = self.timer();
+                // nothing is lost at the start of the cycle
+                if timer.position > 0 || timer.pomodoro_phase == PomodoroPhase::Focus {
+                    self.confirming_reset = true;
+                } else {
+                    return self.update(Message::ConfirmReset);
                 }
             }
+            Message::ConfirmReset => {
+                self.confirming_reset = false;
+                let recorded = self.timer().history.records.len();
+                self.timer_mut().reset();
+                self.add_focus_time(self.active_timer, recorded);
+            }
+            Message::CancelReset => {
+                self.confirming_reset = false;
+            }
             Message::Refresh => {
-                if self.pomodoro_timer.remaining_sec.load(Ordering::SeqCst) == 0u32 {
-                    match self.pomodoro_timer.pomodoro_phase {
-                        PomodoroPhase::BeforeFocus => {}
-                        PomodoroPhase::Focus => {
-                            self.pomodoro_timer.pomodoro_phase = PomodoroPhase::BeforeRelax;
-                            self.pomodoro_timer.stop();
-                            self.pomodoro_timer.remaining_sec.store(self.pomodoro_timer.pomodoro_lengths[self.pomodoro_timer.position].relax, Ordering::SeqCst);
-                            _ = Notification::new()
-                                .summary(&fl!("before-relax"))
-                                .sound_name("window-attention-inactive")
-                                .show();
-                            if self.is_focused() {
-                                self.pomodoro_timer.pomodoro_phase = PomodoroPhase::Relax;
-                                self.pomodoro_timer.start();
-                            }
+                if self.transition_flash.is_some_and(|started| started.elapsed() >= TRANSITION_FLASH_DURATION) {
+                    self.transition_flash = None;
+                }
+                if self.celebration.is_some_and(|started| started.elapsed() >= CELEBRATION_DURATION) {
+                    self.celebration = None;
+                }
+                if self.notification_banner.as_ref().is_some_and(|(_, shown_at)| shown_at.elapsed() >= NOTIFICATION_BANNER_DURATION) {
+                    self.notification_banner = None;
+                }
+                // a running timer is refreshed, so a countdown that died doesn't go unnoticed for long
+                let mut restarted = false;
+                for timer in &mut self.timers {
+                    restarted |= timer.restart_dead_countdown();
+                }
+                if restarted {
+                    self.notification_banner = Some((fl!("countdown-restarted"), Instant::now()));
+                }
+                self.send_micro_reminders();
+            }
+            Message::TimerElapsed(id) => {
+                if let Some(index) = self.timers.iter().position(|timer| timer.id == id) {
+                    let recorded = self.timers[index].history.records.len();
+                    // the position moves on with the phase, so the sound is looked up before
+                    // and as set right now, a sound changed while the phase ran is the one played
+                    let sound_name = self.timers[index].phase_end_sound().to_string();
+                    // cleared when the break ends
+                    let after_completed_focus = self.timers[index].break_after_completed_focus;
+                    if let Some(finished) = self.finish_phase(index) {
+                        self.add_focus_time(index, recorded);
+                        self.notify_phase_end(index, finished, &sound_name);
+                        // see `Config::count_after_break`, there is no break to wait for without breaks
+                        let counted = match finished {
+                            PomodoroPhase::Focus => !self.config.count_after_break || self.timers[index].settings.continuous_focus,
+                            PomodoroPhase::Relax => self.config.count_after_break && after_completed_focus,
+                            PomodoroPhase::BeforeFocus | PomodoroPhase::BeforeRelax => false,
+                        };
+                        if counted {
+                            self.count_completed_focus();
                         }
-                        PomodoroPhase::BeforeRelax => {}
-                        PomodoroPhase::Relax => {
-                            self.pomodoro_timer.position += 1;
-                            if self.pomodoro_timer.position >= self.pomodoro_timer.pomodoro_lengths.len() {
-                                self.pomodoro_timer.position = 0;
-                            }
-                            self.pomodoro_timer.pomodoro_phase = PomodoroPhase::BeforeFocus;
-                            self.pomodoro_timer.stop();
-                            self.pomodoro_timer.remaining_sec.store(self.pomodoro_timer.pomodoro_lengths[self.pomodoro_timer.position].focus, Ordering::SeqCst);
-                            _ = Notification::new()
-                                .summary(&fl!("after-relax"))
-                                .body(&fl!("before-focus"))
-                                .sound_name("alarm-clock-elapsed")
-                                .show();
+                        if index == self.active_timer {
+                            self.transition_flash = Some(Instant::now());
                         }
                     }
                 }
             }
             Message::ChangeSetting(setting_message) => {
-                self.pomodoro_timer.settings.update(setting_message);
+                let command = self.update_setting(setting_message);
+                return Command::batch([command, self.update_titles(), self.sync_break_overlay()]);
+            }
+            Message::ExportHistory => {
+                // the history is kept for the current run only, of all timers
+                let csv = session_history::to_csv(self.timers.iter().map(|timer| &timer.history));
+                return Command::perform(export_history(csv), |result| {
+                    cosmic::app::message::app(Message::HistoryExported(result))
+                });
+            }
+            Message::HistoryExported(result) => {
+                if let Err(error) = result {
+                    tracing::warn!(%error, "failed to export history");
+                }
+            }
+            Message::ExportSummaryCard => {
+                let card = SummaryCard::new(&self.config, Local::now().date_naive());
+                // the button is disabled then, a shortcut may still come through
+                if !card.has_data() {
+                    return Command::none();
+                }
+                return Command::perform(export_summary_card(card), |result| {
+                    cosmic::app::message::app(Message::SummaryCardExported(result))
+                });
+            }
+            Message::SummaryCardExported(result) => {
+                if let Err(error) = result {
+                    tracing::warn!(%error, "failed to export the summary card");
+                }
+            }
+            Message::DismissHint => {
+                self.config.hint_dismissed = true;
+                self.save_config();
+                self.core.window.show_context = false;
+            }
+            Message::NoteChanged(note) => {
+                self.timer_mut().note = note;
+            }
+            Message::DistractionNoteChanged(note) => {
+                self.timer_mut().distraction_note = note;
+            }
+            Message::DailyGoalChanged(input) => {
+                if let Ok(daily_goal) = input.parse::<u32>() {
+                    self.config.daily_goal = daily_goal;
+                    // the longest streak so far was reached with the old goal
+                    self.config.longest_streak = statistics::longest_streak(&self.config.daily_completed, daily_goal);
+                    self.save_config();
+                }
+            }
+            Message::SummaryAtTimeChanged(at_time) => {
+                self.config.summary_at = if at_time {
+                    Some(NaiveTime::parse_from_str(&self.summary_time_input, TIME_FORMAT)
+                        .unwrap_or_else(|_| default_summary_time()))
+                } else {
+                    None
+                };
+                self.save_config();
+            }
+            Message::SummaryTimeChanged(input) => {
+                if let Ok(time) = NaiveTime::parse_from_str(&input, TIME_FORMAT) {
+                    self.config.summary_at = Some(time);
+                    self.save_config();
+                }
+                self.summary_time_input = input;
+            }
+            Message::SummaryOnGoalChanged(on_goal) => {
+                self.config.summary_on_goal = on_goal;
+                self.save_config();
+            }
+            Message::RestorePausedChanged(restore_paused) => {
+                self.config.restore_paused = restore_paused;
+                self.save_config();
+            }
+            Message::RestorePositionOnlyChanged(position_only) => {
+                self.config.restore_position_only = position_only;
+                self.save_config();
+            }
+            Message::HighContrastChanged(high_contrast) => {
+                // from now on independent of the system
+                self.config.high_contrast = Some(high_contrast);
+                self.save_config();
+            }
+            Message::ShortcutHintsChanged(shortcut_hints) => {
+                self.config.shortcut_hints = shortcut_hints;
+                self.save_config();
+            }
+            Message::CountdownInTitleChanged(countdown_in_title) => {
+                self.config.countdown_in_title = countdown_in_title;
+                self.save_config();
+            }
+            Message::TransientBreakNotificationsChanged(transient) => {
+                self.config.transient_break_notifications = transient;
+                self.save_config();
+            }
+            Message::TransientFocusNotificationsChanged(transient) => {
+                self.config.transient_focus_notifications = transient;
+                self.save_config();
+            }
+            Message::SessionTimeWallClockChanged(wall_clock) => {
+                self.config.session_time_wall_clock = wall_clock;
+                self.save_config();
+            }
+            Message::BreakActivityChanged(index, activity) => {
+                if let Some(entry) = self.config.break_activities.get_mut(index) {
+                    *entry = activity;
+                    self.save_config();
+                }
+            }
+            Message::AddBreakActivity => {
+                self.config.break_activities.push(String::new());
+                self.save_config();
+            }
+            Message::DeleteBreakActivity(index) => {
+                if index < self.config.break_activities.len() {
+                    self.config.break_activities.remove(index);
+                    self.save_config();
+                }
+            }
+            Message::WriteStateFileChanged(write_state_file) => {
+                self.config.write_state_file = write_state_file;
+                self.save_config();
+                if write_state_file {
+                    self.state_file_written = None;
+                } else {
+                    state_file::remove();
+                }
+            }
+            Message::FitWindowToContentChanged(fit_window_to_content) => {
+                self.config.fit_window_to_content = fit_window_to_content;
+                self.save_config();
+                // turning it on again takes back a size set by hand
+                self.manually_resized = false;
+                self.fitted_window_size = None;
+            }
+            Message::ReadyNotificationsChanged(ready_notifications) => {
+                self.config.ready_notifications = ready_notifications;
+                self.save_config();
+            }
+            Message::NotificationProgressChanged(notification_progress) => {
+                self.config.notification_progress = notification_progress;
+                self.save_config();
+            }
+            Message::RefreshIntervalChanged(input) => {
+                // clamped when used, so typing a longer number doesn't get cut off on the way
+                if let Ok(interval_ms) = input.parse::<u32>() {
+                    self.config.refresh_interval_ms = interval_ms;
+                    self.save_config();
+                }
+            }
+            Message::Interrupt => {
+                let recorded = self.timer().history.records.len();
+                if self.timer_mut().interrupt() {
+                    *self.config.daily_interruptions.entry(Local::now().date_naive()).or_default() += 1;
+                    self.save_config();
+                    // a void focus period of the purist mode was recorded
+                    self.add_focus_time(self.active_timer, recorded);
+                }
+            }
+            Message::StatsCountPartialChanged(count_partial) => {
+                self.config.stats_count_partial = count_partial;
+                self.save_config();
+            }
+            Message::CountAfterBreakChanged(count_after_break) => {
+                self.config.count_after_break = count_after_break;
+                self.save_config();
+            }
+            Message::CheckIdle => {
+                self.prompt_idle_timers();
+            }
+            Message::CheckDay => {
+                self.reset_on_new_day();
+            }
+            Message::ResetDailyChanged(reset_daily) => {
+                self.config.reset_daily = reset_daily;
+                self.save_config();
+            }
+            Message::CheckSummary => {
+                let now = Local::now();
+                if self.config.summary_at.is_some_and(|summary_at| now.time() >= summary_at) {
+                    self.send_summary(now.date_naive());
+                }
+            }
+            Message::Key(modifiers, key) => {
+                if let Some(action) = self.capturing_key_bind {
+                    self.capture_key_bind(action, modifiers, &key);
+                    return Command::none();
+                }
+                // closes what's on top, without either it's free for a key binding
+                if key == Key::Named(keyboard::key::Named::Escape) && modifiers.is_empty() {
+                    if self.confirming_reset {
+                        return self.update(Message::CancelReset);
+                    }
+                    if self.core.window.show_context {
+                        self.core.window.show_context = false;
+                        return Command::none();
+                    }
+                }
+                for (key_bind, action) in &self.key_binds {
+                    if key_bind.matches(modifiers, &key) {
+                        return self.update(action.message());
+                    }
+                }
+            }
+            Message::WindowResized(width, height) => {
+                self.window_size = Some((width, height));
+                // any other size than the fitted one was set by hand
+                if self.fitted_window_size.is_some_and(|fitted| fitted != (width, height)) {
+                    self.manually_resized = true;
+                }
+            }
+            Message::WindowFocusChanged(focused) => {
+                // one check for each grace period, a timer is paused once its own has run out
+                let graces = self.pause_on_unfocus(focused);
+                if !graces.is_empty() {
+                    return Command::batch(graces.into_iter().map(|grace| {
+                        Command::perform(tokio::time::sleep(grace), |_| cosmic::app::message::app(Message::PauseUnfocused))
+                    }));
+                }
+            }
+            Message::PauseUnfocused => {
+                self.pause_unfocused_timers();
+            }
+            Message::CaptureKeyBind(action) => {
+                self.capturing_key_bind = Some(action);
+                self.key_bind_conflict = None;
+            }
+            Message::QuickStart(secs) => {
+                let recorded = self.timer().history.records.len();
+                self.timer_mut().quick_start(secs);
+                self.add_focus_time(self.active_timer, recorded);
+            }
+            Message::TogglePresentationMode => {
+                self.presentation_mode = !self.presentation_mode;
+            }
+            Message::RunInBackgroundChanged(run_in_background) => {
+                self.config.run_in_background = run_in_background;
+                self.save_config();
+            }
+            Message::MainWindowClosed => {
+                if self.run_in_background {
+                    // the timers keep running, a new launch shows the window again
+                    return window::change_mode(window::Id::MAIN, window::Mode::Hidden);
+                }
+                if self.config.write_state_file {
+                    state_file::remove();
+                }
+                #[cfg(feature = "control-socket")]
+                control_socket::remove();
+                // the break overlay has to go with the app
+                #[cfg(feature = "wayland")]
+                if let Some(id) = self.break_overlay.take() {
+                    return destroy_layer_surface(id);
+                }
+            }
+            Message::AddTimer => {
+                self.timers.push(PomodoroTimer::new());
+                self.select_timer(self.timers.len() - 1);
+            }
+            Message::SelectTimer(index) => {
+                if index < self.timers.len() {
+                    self.select_timer(index);
+                }
+            }
+            Message::CloseTimer(index) => {
+                if self.timers.len() > 1 && index < self.timers.len() {
+                    self.timers.remove(index);
+                    let active_timer = if index < self.active_timer {
+                        self.active_timer - 1
+                    } else {
+                        self.active_timer.min(self.timers.len() - 1)
+                    };
+                    self.select_timer(active_timer);
+                }
             }
         }
-        Command::none()
+        if changes_timers {
+            self.save_timers();
+        }
+        // changes show up right away, the countdown once a second
+        if changes_timers || !self.state_file_written.is_some_and(|written| written.elapsed() < STATE_FILE_INTERVAL) {
+            self.write_state_file();
+        }
+        Command::batch([self.update_titles(), self.sync_break_overlay(), self.fit_window()])
     }
     fn subscription(&self) -> Subscription<Self::Message> {
-        match self.pomodoro_timer.pomodoro_state {
-            PomodoroState::Run => {
-                time::every(Duration::from_millis(250))
-                    .map(|_| Message::Refresh)
+        let events = event::listen_with(|event, status| match event {
+            event::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => match status {
+                // keys typed into a text input are captured
+                event::Status::Ignored => Some(Message::Key(modifiers, key)),
+                event::Status::Captured => None,
+            },
+            event::Event::Window(id, window::Event::Resized { width, height }) if id == window::Id::MAIN => {
+                Some(Message::WindowResized(width, height))
             }
-            PomodoroState::Stop => { Subscription::none() }
-            PomodoroState::Pause => { Subscription::none() }
+            event::Event::Window(id, window::Event::Focused) if id == window::Id::MAIN => {
+                Some(Message::WindowFocusChanged(true))
+            }
+            event::Event::Window(id, window::Event::Unfocused) if id == window::Id::MAIN => {
+                Some(Message::WindowFocusChanged(false))
+            }
+            _ => None,
+        });
+        let summary = if self.config.summary_at.is_some() {
+            time::every(Duration::from_secs(30))
+                .map(|_| Message::CheckSummary)
+        } else {
+            Subscription::none()
+        };
+        let day = if self.config.reset_daily {
+            time::every(Duration::from_secs(60))
+                .map(|_| Message::CheckDay)
+        } else {
+            Subscription::none()
+        };
+        let waiting = self.timers.iter()
+            .any(|timer| timer.settings.idle_prompt_secs > 0 && timer.waiting_since.is_some());
+        let idle = if waiting {
+            time::every(Duration::from_secs(10))
+                .map(|_| Message::CheckIdle)
+        } else {
+            Subscription::none()
+        };
+        let timers = self.timers.iter().map(timer_subscription);
+        #[cfg(feature = "control-socket")]
+        let timers = timers.chain([control_socket::subscription()]);
+        #[cfg(feature = "http-status")]
+        let timers = timers.chain((self.config.http_status_port != 0).then(|| http_status::subscription(self.config.http_status_port)));
+        Subscription::batch(timers.chain([events, summary, day, idle, self.refresh_subscription()]))
+    }
+    /// The break overlay is the only other window.
+    #[cfg(feature = "wayland")]
+    fn view_window(&self, id: window::Id) -> Element<Self::Message> {
+        if self.break_overlay != Some(id) {
+            return widget::text::text("").into();
+        }
+        let timer = self.timer();
+        if timer.settings.break_overlay == BreakOverlay::Dim {
+            return get_dim_view(timer.settings.dim_opacity);
+        }
+        get_break_overlay_view(
+            timer.remaining_sec.load(Ordering::SeqCst),
+            timer.settings.break_overlay == BreakOverlay::Flexible,
+        )
+    }
+
+    /// Only the main window can be closed, the break overlay is closed by the timer.
+    fn on_close_requested(&self, id: window::Id) -> Option<Self::Message> {
+        if id != window::Id::MAIN {
+            return None;
         }
+        Some(Message::MainWindowClosed)
+    }
+
+    /// Another launch shows the window of the instance running in the background.
+    fn dbus_activation(&mut self, _message: DbusActivationMessage) -> Command<Self::Message> {
+        Command::batch([
+            window::change_mode(window::Id::MAIN, window::Mode::Windowed),
+            window::gain_focus(window::Id::MAIN),
+        ])
     }
+
     /// This is the main view of your application, it is the root of your widget tree.
     ///
     /// The `Element` type is used to represent the visual elements of your application,
@@ -255,72 +1161,314 @@ impl Application for CosmicPomodoro {
     ///
     /// To get a better sense of which widgets are available, check out the `widget` module.
     fn view(&self) -> Element<Self::Message> {
+        let timer = self.timer();
         let mut initial_secs: u32 = 0;
-        if self.pomodoro_timer.pomodoro_phase == PomodoroPhase::Relax {
-            initial_secs = self.pomodoro_timer.pomodoro_lengths[self.pomodoro_timer.position].relax;
-        } else if self.pomodoro_timer.pomodoro_phase == PomodoroPhase::Focus {
-            initial_secs = self.pomodoro_timer.pomodoro_lengths[self.pomodoro_timer.position].focus;
+        if timer.pomodoro_phase == PomodoroPhase::Relax
+            || timer.pomodoro_phase == PomodoroPhase::Focus {
+            initial_secs = timer.initial_sec;
         }
-        let remaining_secs = self.pomodoro_timer.remaining_sec.load(Ordering::SeqCst);
+        let remaining_secs = timer.remaining_sec.load(Ordering::SeqCst);
         let cosmic_theme::Spacing { space_m, .. } = theme::active().cosmic().spacing;
         let mut root = widget::column::with_capacity(3).spacing(space_m);
+        let high_contrast = self.is_high_contrast();
+        let breathing = self.is_breathing();
+        let locked = timer.is_locked();
         let play_pause_button: widget::button::Button<'static, Message>;
-        match self.pomodoro_timer.pomodoro_state {
+        match timer.pomodoro_state {
             PomodoroState::Pause | PomodoroState::Stop => {
-                play_pause_button = CosmicPomodoro::get_play_pause_button("play", initial_secs, remaining_secs);
+                play_pause_button = CosmicPomodoro::get_play_pause_button("play", initial_secs, remaining_secs, &timer.settings, high_contrast, breathing);
             }
             PomodoroState::Run => {
-                play_pause_button = CosmicPomodoro::get_play_pause_button("pause", initial_secs, remaining_secs);
+                play_pause_button = CosmicPomodoro::get_play_pause_button("pause", initial_secs, remaining_secs, &timer.settings, high_contrast, breathing);
             }
         }
-        match self.pomodoro_timer.pomodoro_phase {
-            PomodoroPhase::BeforeFocus => {
-                root = root.push(widget::text::heading(fl!("before-focus"))
-                    .size(26)
+        let play_pause_button = if locked { play_pause_button.on_press_maybe(None) } else { play_pause_button };
+        let progress_style = timer.settings.progress_style;
+        // the plain button of the remaining time only isn't stretched to where the ring would be
+        let button_width = if progress_style == ProgressStyle::None { Length::Shrink } else { Length::Fill };
+        let mut play_control = widget::column::with_capacity(2)
+            .push(play_pause_button.width(button_width))
+            .width(Length::Fill)
+            .align_items(Alignment::Center)
+            .spacing(space_m);
+        if progress_style == ProgressStyle::Bar {
+            play_control = play_control.push(iced_widget::progress_bar(0.0..=1.0, progress_percentage(initial_secs, remaining_secs, &timer.settings))
+                .height(Length::Fixed(8.0)));
+        }
+        let heading = timer.pomodoro_phase.localized_name();
+        if self.presentation_mode {
+            root = root.push(widget::text::text(fl!("presentation-mode", "active"))
+                .style(theme::Text::Accent)
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center));
+        }
+        if let Some((message, _)) = &self.notification_banner {
+            root = root.push(widget::text::text(message.as_str())
+                .style(theme::Text::Accent)
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center));
+        }
+        if self.timers.len() > 1 {
+            root = root.push(self.get_timer_tabs());
+        }
+        let mut heading = widget::text::heading(heading)
+            .size(26)
+            .width(Length::Fill)
+            .horizontal_alignment(Horizontal::Center);
+        if high_contrast {
+            heading = heading.style(theme::Text::Color(high_contrast_text_color()));
+        }
+        let heading = widget::tooltip(heading, self.get_schedule_tooltip(), widget::tooltip::Position::Bottom);
+        // the note belongs to the upcoming or running focus period
+        let note = matches!(timer.pomodoro_phase, PomodoroPhase::BeforeFocus | PomodoroPhase::Focus).then(|| {
+            widget::text_input(fl!("note", "placeholder"), &timer.note)
+                .on_input(Message::NoteChanged)
+                .width(Length::Fill)
+        });
+        let remaining_duration = Duration::from_secs(remaining_secs as u64);
+
+        let overtime_duration = timer.is_in_overtime()
+            .then(|| Duration::from_secs(timer.overtime_sec.load(Ordering::SeqCst) as u64));
+        // the seconds alone, big, once a focus period is in its last minute
+        let final_countdown = timer.settings.final_countdown
+            && timer.pomodoro_phase == PomodoroPhase::Focus
+            && overtime_duration.is_none()
+            && remaining_secs < FINAL_COUNTDOWN_SECS;
+        let remaining_label = if final_countdown {
+            localization::format_number(remaining_secs as u64)
+        } else {
+            remaining_label(timer.settings.display_granularity, remaining_duration, overtime_duration)
+        };
+        let mut remaining_text = widget::text::heading(remaining_label)
+            .size(if final_countdown { 72 } else { 26 })
+            .width(Length::Fill)
+            .horizontal_alignment(Horizontal::Center);
+        if high_contrast {
+            // the warning and overtime colors would have less contrast
+            remaining_text = remaining_text.style(theme::Text::Color(high_contrast_text_color()));
+        } else if overtime_duration.is_some() {
+            remaining_text = remaining_text.style(theme::Text::Accent);
+        } else if timer.pomodoro_phase == PomodoroPhase::Focus
+            && remaining_secs < timer.settings.warning_threshold {
+            let warning_color: Color = theme::active().cosmic().warning_color().into();
+            remaining_text = remaining_text.style(theme::Text::Color(warning_color));
+        }
+        // the icons tell the two apart at a glance, the labels say what they do
+        let control_buttons = widget::row::with_capacity(2)
+            .push(with_hint(CosmicPomodoro::get_icon_button("stop", fl!("reset"), (!locked).then_some(Message::RequestReset)),
+                            self.shortcut_hint(MenuAction::ResetTimer)))
+            .push(with_hint(CosmicPomodoro::get_icon_button("skip", fl!("skip"), (!locked && !timer.keeps_break()).then_some(Message::SkipPhase)),
+                            self.shortcut_hint(MenuAction::SkipPhase)))
+            .spacing(space_m)
+            .apply(widget::container)
+            .width(Length::Fill)
+            .align_x(Horizontal::Center);
+        let interrupt_button = (timer.pomodoro_phase == PomodoroPhase::Focus && timer.pomodoro_state == PomodoroState::Run).then(|| {
+            // noting what pulled you away is optional, enter counts the interruption as well
+            widget::row::with_capacity(2)
+                .push(widget::text_input(fl!("interrupt", "placeholder"), &timer.distraction_note)
+                    .on_input(Message::DistractionNoteChanged)
+                    .on_submit(Message::Interrupt)
+                    .width(Length::Fill))
+                .push(widget::button::text(fl!("interrupt", count = timer.interruptions))
+                    .on_press(Message::Interrupt))
+                .align_items(Alignment::Center)
+                .spacing(space_m)
+        });
+        // says why the buttons don't react
+        let lock_note = locked.then(|| {
+            widget::text::caption(fl!("focus-locked"))
+                .style(theme::Text::Accent)
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+        });
+        // greyed out in the purist mode, which doesn't bank time
+        let bank_button = (timer.pomodoro_phase == PomodoroPhase::Focus && !timer.settings.continuous_focus).then(|| {
+            widget::button::text(fl!("bank-and-break"))
+                .on_press_maybe((!locked && !timer.settings.purist).then_some(Message::BankAndBreak))
+                .apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+        });
+        let banked = (timer.banked_sec > 0).then(|| {
+            let time = localization::localize_digits(&Duration::from_secs(timer.banked_sec as u64).to_timer_string());
+            widget::text::caption(fl!("banked", time = time))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+        });
+        let one_offs = (timer.one_offs_waiting() > 0).then(|| {
+            widget::text::caption(fl!("one-more-waiting", count = timer.one_offs_waiting()))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+        });
+        // what comes after the break, kept out of sight during focus
+        let next_up = matches!(timer.pomodoro_phase, PomodoroPhase::BeforeRelax | PomodoroPhase::Relax).then(|| {
+            let focus = localization::localize_digits(&Duration::from_secs(timer.next_focus_secs() as u64).to_timer_string());
+            let completed = self.config.daily_completed.get(&Local::now().date_naive()).copied().unwrap_or(0);
+            let text = if self.config.daily_goal == 0 {
+                fl!("next-up", focus = focus)
+            } else {
+                fl!("next-up", "goal", focus = focus,
+                    completed = completed,
+                    goal = self.config.daily_goal)
+            };
+            widget::text::caption(text)
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+        });
+        let long_break = timer.time_until_long_break().map(|duration| {
+            widget::text::caption(fl!("long-break-in", time = localization::localize_digits(&duration.to_timer_string())))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+        });
+        let session_time = self.work_session.as_ref()
+            .filter(|session| session.day == Local::now().date_naive())
+            .map(|session| {
+                let elapsed = session.elapsed(Instant::now(), self.any_timer_running(), self.config.session_time_wall_clock);
+                widget::text::caption(fl!("session-time", time = localization::localize_digits(&elapsed.to_hours_string())))
                     .width(Length::Fill)
-                    .horizontal_alignment(Horizontal::Center))
+                    .horizontal_alignment(Horizontal::Center)
+            });
+        let celebration = self.celebration.map(|started| {
+            // pulses twice a second while it lasts
+            let pulse = (started.elapsed().as_secs_f32() * 2.0 * std::f32::consts::TAU).sin();
+            widget::text::title3(fl!("cycle-complete"))
+                .size(24.0 + 4.0 * pulse)
+                .style(theme::Text::Accent)
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+        });
+        // the end moves on while paused, so it isn't shown then
+        let finish_time = if timer.pomodoro_state == PomodoroState::Pause {
+            "—".to_string()
+        } else {
+            let finish = Local::now() + chrono::Duration::from_std(timer.time_until_cycle_end()).unwrap_or_default();
+            localization::format_time_of_day(finish)
+        };
+        let finish_time = widget::text::caption(fl!("cycle-finishes-at", time = finish_time))
+            .width(Length::Fill)
+            .horizontal_alignment(Horizontal::Center);
+        let clock = timer.settings.show_clock.then(|| {
+            let time = localization::format_time_of_day(Local::now());
+            widget::text::text(fl!("clock", time = time))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center)
+        });
+
+        if self.is_horizontal_layout() {
+            // the ring next to the text, for wide and short windows
+            let mut info = widget::column::with_capacity(6);
+            if let Some(celebration) = celebration {
+                info = info.push(celebration);
             }
-            PomodoroPhase::Focus => {
-                root = root.push(widget::text::heading(fl!("focus-running"))
-                    .size(26)
-                    .width(Length::Fill)
-                    .horizontal_alignment(Horizontal::Center))
+            info = info.push(heading);
+            if let Some(note) = note {
+                info = info.push(note);
             }
-            PomodoroPhase::BeforeRelax => {
-                root = root.push(widget::text::heading(fl!("before-relax"))
-                    .size(26)
-                    .width(Length::Fill)
-                    .horizontal_alignment(Horizontal::Center))
+            info = info
+                .push(remaining_text)
+                .push(control_buttons);
+            if let Some(lock_note) = lock_note {
+                info = info.push(lock_note);
             }
-            PomodoroPhase::Relax => {
-                root = root.push(widget::text::heading(fl!("relax-running"))
-                    .size(26)
-                    .width(Length::Fill)
-                    .horizontal_alignment(Horizontal::Center))
+            if let Some(interrupt_button) = interrupt_button {
+                info = info.push(interrupt_button);
+            }
+            if let Some(bank_button) = bank_button {
+                info = info.push(bank_button);
+            }
+            info = info
+                .push(self.get_progress_dots())
+                .spacing(space_m);
+            if let Some(banked) = banked {
+                info = info.push(banked);
+            }
+            if let Some(one_offs) = one_offs {
+                info = info.push(one_offs);
+            }
+            if let Some(next_up) = next_up {
+                info = info.push(next_up);
+            }
+            if let Some(long_break) = long_break {
+                info = info.push(long_break);
+            }
+            info = info.push(finish_time);
+            if let Some(session_time) = session_time {
+                info = info.push(session_time);
+            }
+            if let Some(clock) = clock {
+                info = info.push(clock);
+            }
+            root = root.push(widget::row::with_capacity(2)
+                .push(with_hint(play_control, self.shortcut_hint(MenuAction::StartTimer))
+                    .width(Length::FillPortion(2)))
+                .push(info.width(Length::FillPortion(3)))
+                .align_items(Alignment::Center)
+                .spacing(space_m)
+            );
+        } else {
+            if let Some(celebration) = celebration {
+                root = root.push(celebration);
+            }
+            root = root.push(heading);
+            if let Some(note) = note {
+                root = root.push(note);
+            }
+            root = root.push(widget::row::with_children(
+                vec![widget::column().width(Length::Fill).into(),
+                     with_hint(play_control, self.shortcut_hint(MenuAction::StartTimer))
+                         .width(Length::FillPortion(2)).into(),
+                     widget::column().width(Length::Fill).into()
+                ]
+            ));
+            root = root.push(remaining_text);
+            root = root.push(control_buttons);
+            if let Some(lock_note) = lock_note {
+                root = root.push(lock_note);
+            }
+            if let Some(interrupt_button) = interrupt_button {
+                root = root.push(interrupt_button);
+            }
+            if let Some(bank_button) = bank_button {
+                root = root.push(bank_button);
+            }
+            root = root.push(self.get_progress_dots());
+            if let Some(banked) = banked {
+                root = root.push(banked);
+            }
+            if let Some(one_offs) = one_offs {
+                root = root.push(one_offs);
+            }
+            if let Some(next_up) = next_up {
+                root = root.push(next_up);
+            }
+            if let Some(long_break) = long_break {
+                root = root.push(long_break);
+            }
+            root = root.push(finish_time);
+            if let Some(session_time) = session_time {
+                root = root.push(session_time);
+            }
+            if let Some(clock) = clock {
+                root = root.push(clock);
             }
         }
-        root = root.push(widget::row::with_children(
-            vec![widget::column().width(Length::Fill).into(),
-                 play_pause_button.width(Length::FillPortion(2)).into(),
-                 widget::column().width(Length::Fill).into()
-            ]
-        ));
-        let remaining_duration = Duration::from_secs(remaining_secs as u64);
-
-        let formated_remaining = format!("{:02}:{:02}", remaining_duration.as_minutes(), remaining_duration.as_seconds());
-        root = root.push(widget::text::heading(formated_remaining)
-            .size(26)
-            .width(Length::Fill)
-            .horizontal_alignment(Horizontal::Center)
-        );
 
-
-        root.apply(widget::container)
+        let mut content = root.apply(widget::container)
             .width(Length::Fill)
             .height(Length::Fill)
             .align_x(Horizontal::Center)
-            .align_y(Vertical::Center)
-            .into()
+            .align_y(Vertical::Center);
+        if let Some(started) = self.transition_flash {
+            let fade = 1.0 - started.elapsed().as_secs_f32() / TRANSITION_FLASH_DURATION.as_secs_f32();
+            let mut color: Color = theme::active().cosmic().accent_color().into();
+            color.a = 0.5 * fade.clamp(0.0, 1.0);
+            content = content.style(theme::Container::custom(move |_| container::Appearance {
+                background: Some(Background::Color(color)),
+                ..Default::default()
+            }));
+        }
+        content.into()
     }
 }
 
@@ -350,79 +1498,761 @@ impl CosmicPomodoro {
             .into()
     }
 
-    /// Updates the header and window titles.
-    pub fn update_titles(&mut self) -> Command<Message> {
-        let window_title = fl!("app-title");
+    /// Explains the phase flow on first launch.
+    pub fn hint(&self) -> Element<Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
 
-        self.set_window_title(window_title)
+        widget::column()
+            .push(widget::text::title3(fl!("hint", "title")))
+            .push(widget::text::text(fl!("hint", "phases")))
+            .push(widget::text::text(fl!("hint", "settings")))
+            .push(widget::button::suggested(fl!("hint", "dismiss"))
+                .on_press(Message::DismissHint))
+            .spacing(space_xxs)
+            .into()
     }
 
-    fn get_play_pause_button(button_name : &'static str, initial_secs: u32, remaining_secs: u32) -> widget::button::Button<'static, Message> {
-        let percentage = 1.0 -  remaining_secs as f32 / initial_secs as f32;
-        let radian = 2.0 * std::f32::consts::PI * percentage;
-        let icon_svg = icon_cache::get_icon_cache_svg(button_name);
-        let content = str::from_utf8(icon_svg.as_ref()).unwrap();
-        let mut reader = Reader::from_str(content);
-        let mut writer = Writer::new(Cursor::new(Vec::new()));
-        loop {
-            match reader.read_event() {
-                Ok(Event::Empty(e)) if e.attributes().any(|attr|
-                    {
-                        if !attr.is_ok() {
-                            return false;
-                        }
-                        let attr = attr.unwrap();
-                        attr.key.local_name().as_ref() == b"id" && attr.value.as_ref() == b"progress-circle"
-                    }) => {
-
-                    let mut elem = BytesStart::new("path");
-
-                    // collect existing attributes except d
-                    elem.extend_attributes(e.attributes()
-                        .map(|attr| attr.unwrap())
-                        .filter(|attr| attr.key.local_name().as_ref() != b"d")
-                    );
+    /// Updates the header and window titles with the phase of the active timer.
+    /// The remaining time is in the title as well if turned on, for the taskbar and the overview,
+    /// whose thumbnails may not be redrawn while the window is minimized, the title still is.
+    pub fn update_titles(&mut self) -> Command<Message> {
+        let timer = self.timer();
+        let phase = timer.pomodoro_phase.localized_name();
+        let counting = matches!(timer.pomodoro_phase, PomodoroPhase::Focus | PomodoroPhase::Relax);
+        let window_title = if self.config.countdown_in_title && counting {
+            let remaining = Duration::from_secs(timer.remaining_sec.load(Ordering::SeqCst) as u64);
+            let overtime = timer.is_in_overtime()
+                .then(|| Duration::from_secs(timer.overtime_sec.load(Ordering::SeqCst) as u64));
+            fl!("window-title", "countdown", remaining = remaining_label(timer.settings.display_granularity, remaining, overtime), phase = phase)
+        } else {
+            fl!("window-title", phase = phase)
+        };
+        // most messages change neither the phase nor the remaining time shown
+        if window_title == self.window_title {
+            return Command::none();
+        }
+        self.window_title = window_title.clone();
 
-                    let data = e.try_get_attribute("d").unwrap().unwrap();
-                    let data_string = str::from_utf8(data.value.as_ref()).unwrap();
-                    let mut parts = data_string.split(' ').collect::<Vec<_>>();
+        self.set_window_title(window_title)
+    }
 
-                    let a_position = parts.iter().position(|&part| part.eq("A"));
-                    if a_position.is_none() {
-                        continue;
-                    }
+    /// Resizes the window to fit the view, if turned on and the window wasn't resized by hand.
+    fn fit_window(&mut self) -> Command<Message> {
+        if !self.config.fit_window_to_content || self.manually_resized {
+            return Command::none();
+        }
+        let (mut width, mut height) = if self.timer().settings.layout == Layout::Horizontal {
+            FITTED_HORIZONTAL_SIZE
+        } else {
+            FITTED_VERTICAL_SIZE
+        };
+        if self.core.window.show_context {
+            width += FITTED_DRAWER_WIDTH;
+            height = height.max(FITTED_DRAWER_HEIGHT);
+        }
+        if self.fitted_window_size == Some((width, height)) {
+            return Command::none();
+        }
+        self.fitted_window_size = Some((width, height));
+        window::resize(window::Id::MAIN, Size::new(width as f32, height as f32))
+    }
 
-                    let a_position = a_position.unwrap();
-                    let radius = parts[a_position + 1].parse::<f32>().unwrap();
-                    let large_arc_postion = a_position + 4;
-                    let x_position = a_position + 6;
-                    let y_position = a_position + 7;
-                    if percentage > 0.5 {
-                        parts[large_arc_postion] = "1";
-                    } else {
-                        parts[large_arc_postion] = "0";
-                    }
-                    let x = (260.0 + radian.cos() * radius).to_string();
-                    parts[x_position] = &x;
-                    let y = (260.0 + radian.sin() * radius).to_string();
-                    parts[y_position] = &y;
-                    let path = parts.join(" ");
-                    elem.push_attribute(("d", path.as_str()));
-                    // writes the event to the writer
-                    writer.write_event(Event::Empty(elem)).expect("xml writer error");
-                }
-                Ok(Event::Eof) => break,
-                // we can either move or borrow the event to write, depending on your use-case
-                Ok(e) => assert!(writer.write_event(e).is_ok()),
-                Err(e) => panic!("Error at position {}: {:?}", reader.error_position(), e),
-            }
-        }
-        let icon_svg = writer.into_inner().into_inner();
-        widget::button(widget::svg(iced_widget::svg::Handle::from_memory(icon_svg)).content_fit(ContentFit::Contain))
+    /// The button as the progress style draws it, the bar of [`ProgressStyle::Bar`] is left to the view.
+    fn get_play_pause_button(button_name : &'static str, initial_secs: u32, remaining_secs: u32, settings: &Settings, high_contrast: bool, breathing: bool) -> widget::button::Button<'static, Message> {
+        if settings.progress_style == ProgressStyle::None {
+            let label = if button_name == "pause" { fl!("pause") } else { fl!("start") };
+            return widget::button::text(label).on_press(Message::StartTimer);
+        }
+        let percentage = progress_percentage(initial_secs, remaining_secs, settings);
+        let (stroke_width, ring_color) = if high_contrast {
+            (settings.ring_stroke_width as f32 * HIGH_CONTRAST_STROKE_FACTOR, Some(HIGH_CONTRAST_RING_COLOR))
+        } else {
+            (settings.ring_stroke_width as f32, None)
+        };
+        // swells and thins by a quarter, on the wall clock so every redraw continues the breath
+        let stroke_width = if breathing {
+            let period = PAUSE_PULSE_PERIOD.as_millis() as i64;
+            let phase = Local::now().timestamp_millis().rem_euclid(period) as f32 / period as f32;
+            stroke_width * (1.0 + 0.25 * (phase * std::f32::consts::TAU).sin())
+        } else {
+            stroke_width
+        };
+        // the icons are 520 wide with the ring around their center at 260
+        let radius = (RING_RADIUS * settings.ring_size as f32 / 100.0).min(260.0 - stroke_width / 2.0);
+        let icon_svg = icon_cache::get_icon_cache_svg(button_name);
+        let drawn = match settings.progress_style {
+            ProgressStyle::Ring => draw_progress_ring(icon_svg.as_ref(), percentage, radius, stroke_width, ring_color, false),
+            ProgressStyle::Pie => draw_progress_ring(icon_svg.as_ref(), percentage, radius, stroke_width, ring_color, true),
+            ProgressStyle::Bar | ProgressStyle::None => None,
+        };
+        // a custom icon that can't be read is shown as it is, without progress
+        let handle = match drawn {
+            Some(icon_svg) => iced_widget::svg::Handle::from_memory(icon_svg),
+            None => icon_cache::get_icon_cache_handle(button_name),
+        };
+        widget::button(widget::svg(handle).content_fit(ContentFit::Contain))
             .width(Length::Fill)
             .style(cosmic::style::Button::IconVertical)
             .on_press(Message::StartTimer)
     }
+    fn refresh_subscription(&self) -> Subscription<Message> {
+        if self.transition_flash.is_some() || self.celebration.is_some() {
+            // animates the fade out of the flash and the celebration
+            return time::every(Duration::from_millis(50))
+                .map(|_| Message::Refresh);
+        }
+        // the wall-clock session time goes on while nothing runs
+        let session_ticking = self.config.session_time_wall_clock && self.work_session.is_some();
+        // redraws the countdown of all timers, phase transitions come from their events
+        // it ticks while the window is minimized too, which keeps the countdown in the title going
+        if self.any_timer_running() {
+            let interval_ms = self.config.refresh_interval_ms.clamp(*REFRESH_INTERVAL_RANGE.start(), *REFRESH_INTERVAL_RANGE.end());
+            time::every(Duration::from_millis(interval_ms as u64))
+                .map(|_| Message::Refresh)
+        } else if self.is_breathing() {
+            time::every(PAUSE_PULSE_REFRESH)
+                .map(|_| Message::Refresh)
+        } else if self.timer().settings.show_clock || session_ticking || self.notification_banner.is_some() {
+            time::every(Duration::from_secs(1))
+                .map(|_| Message::Refresh)
+        } else {
+            Subscription::none()
+        }
+    }
+    /// A random break activity to suggest, `None` if there are none apart from empty entries.
+    fn pick_break_activity(&self) -> Option<String> {
+        let activities: Vec<&String> = self.config.break_activities.iter()
+            .filter(|activity| !activity.trim().is_empty())
+            .collect();
+        if activities.is_empty() {
+            return None;
+        }
+        let activity = activities[fastrand::usize(..activities.len())];
+        Some(fl!("break-activities", "suggestion", activity = activity.trim()))
+    }
+    /// Whether the ring of the shown timer breathes, see `Settings::pulse_when_paused`.
+    fn is_breathing(&self) -> bool {
+        self.timer().pomodoro_state == PomodoroState::Pause && self.timer().settings.pulse_when_paused
+    }
+    /// Whether to draw with the most contrast, as set or as the system theme prefers.
+    fn is_high_contrast(&self) -> bool {
+        self.config.high_contrast.unwrap_or_else(|| theme::active().cosmic().is_high_contrast)
+    }
+    fn any_timer_running(&self) -> bool {
+        self.timers.iter().any(|timer| timer.pomodoro_state == PomodoroState::Run)
+    }
+    /// Starts today's work session on the first started timer of the day.
+    fn start_work_session(&mut self) {
+        let today = Local::now().date_naive();
+        if self.config.cycle_day != Some(today) {
+            self.config.cycle_day = Some(today);
+            self.save_config();
+        }
+        if self.work_session.as_ref().map_or(true, |session| session.day != today) {
+            self.work_session = Some(WorkSession::new(today, Instant::now()));
+        }
+    }
+    /// Starts the cycle of all timers over once the timers were last started on an earlier day.
+    /// A session running or paused across midnight goes on, the reset waits until all timers are stopped.
+    fn reset_on_new_day(&mut self) {
+        let today = Local::now().date_naive();
+        if !self.config.reset_daily || self.config.cycle_day.map_or(true, |day| day >= today) {
+            return;
+        }
+        if self.timers.iter().any(|timer| timer.pomodoro_state != PomodoroState::Stop) {
+            return;
+        }
+        for index in 0..self.timers.len() {
+            // counts what the previous day left in the history before starting over
+            let recorded = self.timers[index].history.records.len();
+            self.timers[index].reset();
+            self.add_focus_time(index, recorded);
+        }
+        self.config.cycle_day = Some(today);
+        self.save_config();
+    }
+    /// Moves a running timer on to the next phase once its countdown reached zero.
+    /// Returns the phase that was completed.
+    fn finish_phase(&mut self, index: usize) -> Option<PomodoroPhase> {
+        let is_focused = self.is_focused();
+        let is_active = index == self.active_timer;
+        let timer = &mut self.timers[index];
+        // the timer may have been changed since the event was sent
+        if !timer.has_run_out() {
+            return None;
+        }
+        let phase = timer.pomodoro_phase;
+        match phase {
+            PomodoroPhase::BeforeFocus | PomodoroPhase::BeforeRelax => return None,
+            PomodoroPhase::Focus if timer.settings.continuous_focus => {
+                timer.enter_next_focus(true);
+            }
+            PomodoroPhase::Focus => {
+                let auto_start = timer.settings.auto_start_break_when_focused && is_focused;
+                if timer.settings.overtime && !auto_start {
+                    timer.enter_overtime();
+                } else {
+                    timer.enter_before_relax(true);
+                    if auto_start {
+                        timer.pomodoro_phase = PomodoroPhase::Relax;
+                        timer.start();
+                    }
+                }
+            }
+            PomodoroPhase::Relax => {
+                timer.enter_before_focus(true);
+                // the break of the last interval is over, so the cycle starts over
+                if timer.position == 0 && timer.settings.celebrate_cycle && is_active {
+                    self.celebration = Some(Instant::now());
+                }
+            }
+        }
+        // a phase that started on its own waits for the quiet hours to be over, overtime is the old phase
+        if timer.pomodoro_state == PomodoroState::Run && !timer.is_in_overtime()
+            && timer.settings.quiet_pause && timer.settings.is_quiet_time(Local::now().time()) {
+            timer.pause();
+        }
+        for hook in &self.transition_hooks {
+            hook.on_transition(phase, timer.pomodoro_phase);
+        }
+        Some(phase)
+    }
+    /// Reminds of e.g. drinking water every few minutes of a running focus period, without changing the phase.
+    /// Counts the reminders due rather than matching exact seconds, so a late refresh neither skips nor repeats one.
+    fn send_micro_reminders(&mut self) {
+        let mut due = false;
+        for timer in &mut self.timers {
+            let interval = timer.settings.micro_reminder_interval_secs;
+            if interval == 0 || timer.pomodoro_phase != PomodoroPhase::Focus || timer.pomodoro_state != PomodoroState::Run {
+                continue;
+            }
+            let remaining_sec = timer.remaining_sec.load(Ordering::SeqCst);
+            let reminders = timer.initial_sec.saturating_sub(remaining_sec) / interval;
+            if reminders > timer.micro_reminders_sent {
+                timer.micro_reminders_sent = reminders;
+                // the end of the focus period is notified anyway
+                due |= remaining_sec > MICRO_REMINDER_QUIET_SECS;
+            }
+        }
+        if due && !self.presentation_mode {
+            let mut notification = Notification::new();
+            notification.summary(&fl!("micro-reminder"))
+                .urgency(Urgency::Low);
+            self.show_notification(&notification);
+        }
+    }
+    /// Asks whether the user is still there, once per prompt interval a timer keeps waiting for its next phase.
+    /// The prompts stay the same gentle sound and flash however long it waits.
+    fn prompt_idle_timers(&mut self) {
+        let mut due = false;
+        for (index, timer) in self.timers.iter_mut().enumerate() {
+            let interval = timer.settings.idle_prompt_secs;
+            let Some(waiting_since) = timer.waiting_since else {
+                continue;
+            };
+            if interval == 0 || !matches!(timer.pomodoro_phase, PomodoroPhase::BeforeFocus | PomodoroPhase::BeforeRelax) {
+                continue;
+            }
+            let prompts = (waiting_since.elapsed().as_secs() / interval as u64) as u32;
+            if prompts > timer.idle_prompts_sent {
+                timer.idle_prompts_sent = prompts;
+                due = true;
+                if index == self.active_timer {
+                    self.transition_flash = Some(Instant::now());
+                }
+            }
+        }
+        if !due || self.presentation_mode {
+            return;
+        }
+        let mut notification = Notification::new();
+        notification.summary(&fl!("idle-prompt"))
+            .body(&fl!("idle-prompt", "body"))
+            .urgency(Urgency::Low);
+        if !do_not_disturb::is_active() {
+            notification.sound_name("dialog-question");
+        }
+        self.show_notification(&notification);
+    }
+    /// Shows `notification`, or its text in the window if it can't be shown, e.g. without a notification daemon.
+    fn show_notification(&mut self, notification: &Notification) -> Option<NotificationHandle> {
+        match notification.show() {
+            Ok(handle) => Some(handle),
+            Err(error) => {
+                let sound_name = notification.hints.iter().find_map(|hint| match hint {
+                    Hint::SoundName(sound_name) => Some(sound_name.as_str()),
+                    _ => None,
+                });
+                tracing::warn!(summary = %notification.summary, sound_name, %error, "failed to show a notification");
+                let message = if notification.body.is_empty() {
+                    notification.summary.clone()
+                } else {
+                    format!("{} {}", notification.summary, notification.body)
+                };
+                self.notification_banner = Some((message, Instant::now()));
+                None
+            }
+        }
+    }
+    /// Notifies that `phase` of the timer at `index` ended, as chosen by the notification mode.
+    /// Every ended phase is announced with the phase that follows, waiting to be started or already running.
+    fn notify_phase_end(&mut self, index: usize, phase: PomodoroPhase, sound_name: &str) {
+        let entered = self.timers[index].pomodoro_phase;
+        let is_ready = matches!(entered, PomodoroPhase::BeforeFocus | PomodoroPhase::BeforeRelax);
+        if self.presentation_mode || (is_ready && !self.config.ready_notifications) {
+            return;
+        }
+        let quiet = self.timers[index].settings.is_quiet_time(Local::now().time());
+        if quiet && !self.timers[index].settings.quiet_notifications {
+            return;
+        }
+        let play_sound = !quiet && !do_not_disturb::is_active();
+        // the first time comes with the notification, or on its own without one
+        let repeats = self.timers[index].settings.sound_repeat.saturating_sub(1);
+        match self.config.notification_mode {
+            NotificationMode::None => return,
+            NotificationMode::SoundOnly => {
+                if play_sound {
+                    sound::play(sound_name);
+                    sound::repeat(sound_name, repeats);
+                }
+                return;
+            }
+            NotificationMode::VisualOnly | NotificationMode::Both => {}
+        }
+        let (summary, body, transient) = match (phase, entered) {
+            (PomodoroPhase::Focus, PomodoroPhase::Focus) if self.timers[index].is_in_overtime() => {
+                (fl!("overtime-started"), None, self.config.transient_focus_notifications)
+            }
+            // without breaks the next focus period started right away
+            (PomodoroPhase::Focus, PomodoroPhase::Focus) => (fl!("next-focus"), None, self.config.transient_focus_notifications),
+            (PomodoroPhase::Focus, _) => {
+                let body = match self.pick_break_activity() {
+                    Some(activity) => format!("{} {}", entered.localized_name(), activity),
+                    None => entered.localized_name(),
+                };
+                (fl!("after-focus"), Some(body), self.config.transient_break_notifications)
+            }
+            (PomodoroPhase::Relax, _) => (fl!("after-relax"), Some(entered.localized_name()), self.config.transient_focus_notifications),
+            (PomodoroPhase::BeforeFocus | PomodoroPhase::BeforeRelax, _) => return,
+        };
+        let summary = if self.config.notification_progress {
+            let timer = &self.timers[index];
+            fl!("notification-progress", "summary", summary = summary,
+                completed = timer.completed_in_cycle(),
+                total = timer.pomodoro_lengths.len())
+        } else {
+            summary
+        };
+        let mut notification = Notification::new();
+        notification.summary(&summary);
+        notification.hint(Hint::Transient(transient));
+        if let Some(body) = &body {
+            notification.body(body);
+        }
+        if play_sound && self.config.notification_mode == NotificationMode::Both {
+            notification.sound_name(sound_name);
+            sound::repeat(sound_name, repeats);
+        }
+        // replaces the notification of the previous phase instead of stacking up
+        let previous = self.phase_notification.take();
+        if let Some(previous) = &previous {
+            notification.id(previous.id());
+        }
+        let Some(handle) = self.show_notification(&notification) else {
+            return;
+        };
+        if let Some(previous) = previous {
+            // the server doesn't support replacing, so the previous notification is still shown
+            if previous.id() != handle.id() {
+                previous.close();
+            }
+        }
+        self.phase_notification = Some(handle);
+    }
+    fn update_setting(&mut self, message: SettingMessage) -> Command<Message> {
+        // the intervals and some of the settings are saved with the timers, see `SavedSettings`
+        let changes_saved = matches!(message,
+            SettingMessage::IntervalFocusChanged(..) | SettingMessage::IntervalRelaxChanged(..)
+            | SettingMessage::IntervalFocusSoundChanged(..) | SettingMessage::IntervalRelaxSoundChanged(..)
+            | SettingMessage::PuristChanged(_) | SettingMessage::FocusIntensityChanged(_)
+            | SettingMessage::AddInterval | SettingMessage::DeleteInterval(_) | SettingMessage::UndoDeleteInterval
+            | SettingMessage::StartPhaseChanged(_)
+            | SettingMessage::QuietHoursChanged(_) | SettingMessage::QuietStartChanged(_) | SettingMessage::QuietEndChanged(_)
+            | SettingMessage::QuietPauseChanged(_) | SettingMessage::QuietNotificationsChanged(_)
+            | SettingMessage::EndOfFocusSoundChanged(_) | SettingMessage::EndOfRelaxSoundChanged(_)
+            | SettingMessage::FocusLockChanged(_)
+            | SettingMessage::SoundRepeatChanged(_)
+            | SettingMessage::LunchBreakChanged(_) | SettingMessage::LunchStartChanged(_) | SettingMessage::LunchEndChanged(_)
+            | SettingMessage::LunchBreakLengthChanged(_));
+        match message {
+            SettingMessage::IntervalFocusChanged(index, value) => {
+                if let (Ok(secs), Some(length)) = (value.parse::<u32>(), self.timer_mut().pomodoro_lengths.get_mut(index)) {
+                    let clamped = duration_string::clamp_length(secs);
+                    if let Some(clamped) = clamped {
+                        length.focus = clamped;
+                    }
+                    self.timer_mut().settings.length_error = (clamped != Some(secs)).then_some(index);
+                }
+            }
+            SettingMessage::IntervalRelaxChanged(index, value) => {
+                if let (Ok(secs), Some(length)) = (value.parse::<u32>(), self.timer_mut().pomodoro_lengths.get_mut(index)) {
+                    let clamped = duration_string::clamp_length(secs);
+                    if let Some(clamped) = clamped {
+                        length.relax = clamped;
+                    }
+                    self.timer_mut().settings.length_error = (clamped != Some(secs)).then_some(index);
+                }
+            }
+            SettingMessage::IntervalFocusSoundChanged(index, sound) => {
+                if let Some(length) = self.timer_mut().pomodoro_lengths.get_mut(index) {
+                    length.focus_end_sound = sound;
+                }
+            }
+            SettingMessage::IntervalRelaxSoundChanged(index, sound) => {
+                if let Some(length) = self.timer_mut().pomodoro_lengths.get_mut(index) {
+                    length.relax_end_sound = sound;
+                }
+            }
+            SettingMessage::NotificationModeChanged(notification_mode) => {
+                self.config.notification_mode = notification_mode;
+                self.save_config();
+            }
+            SettingMessage::PuristChanged(purist) => {
+                self.timer_mut().set_purist(purist);
+            }
+            SettingMessage::FocusIntensityChanged(intensity) => {
+                self.timer_mut().set_lengths(intensity.lengths());
+            }
+            SettingMessage::AddInterval => {
+                let timer = self.timer_mut();
+                let length = timer.pomodoro_lengths.last().cloned()
+                    .unwrap_or_else(|| PomodoroLength::new(25 * 60, 5 * 60));
+                timer.pomodoro_lengths.push(length);
+            }
+            SettingMessage::DeleteInterval(index) => {
+                if let Some(length) = self.timer_mut().remove_interval(index) {
+                    self.deleted_interval = Some(DeletedInterval {
+                        index,
+                        length,
+                        deleted_at: Instant::now(),
+                    });
+                    self.save_timers();
+                    return Command::perform(tokio::time::sleep(UNDO_DELETE_WINDOW), |_| {
+                        cosmic::app::message::app(Message::ChangeSetting(SettingMessage::DeletedIntervalExpired))
+                    });
+                }
+            }
+            SettingMessage::UndoDeleteInterval => {
+                if let Some(deleted) = self.deleted_interval.take() {
+                    self.timer_mut().insert_interval(deleted.index, deleted.length);
+                }
+            }
+            SettingMessage::DeletedIntervalExpired => {
+                // a newer deletion restarts the window, so only clear once it has really elapsed
+                if self.deleted_interval.as_ref().is_some_and(|deleted| deleted.deleted_at.elapsed() >= UNDO_DELETE_WINDOW) {
+                    self.deleted_interval = None;
+                }
+            }
+            setting_message => self.timer_mut().settings.update(setting_message),
+        }
+        if changes_saved {
+            self.save_timers();
+        }
+        Command::none()
+    }
+    /// Binds the pressed key to `action`, unless another action already uses it.
+    /// Escape cancels the capture.
+    fn capture_key_bind(&mut self, action: BindableAction, modifiers: Modifiers, key: &Key) {
+        if *key == Key::Named(keyboard::key::Named::Escape) {
+            self.capturing_key_bind = None;
+            return;
+        }
+        // keep capturing until a bindable key is pressed, e.g. after a modifier on its own
+        let Some(stored) = StoredKeyBind::from_key_press(modifiers, key) else {
+            return;
+        };
+        let Some(key_bind) = stored.to_key_bind() else {
+            return;
+        };
+        self.capturing_key_bind = None;
+        let own_action = bound_menu_action(action);
+        if self.key_binds.get(&key_bind).is_some_and(|bound| *bound != own_action) {
+            self.key_bind_conflict = Some(fl!("key-bindings", "conflict", key = key_bind.to_string()));
+            return;
+        }
+        self.key_bind_conflict = None;
+        self.config.key_bindings.insert(action, stored);
+        self.key_binds = key_binds(&self.config.key_bindings);
+        self.save_config();
+    }
+    /// Shows the break overlay while the active timer is in a break, if it is turned on.
+    #[cfg(feature = "wayland")]
+    fn sync_break_overlay(&mut self) -> Command<Message> {
+        let timer = self.timer();
+        let in_break = timer.settings.break_overlay != BreakOverlay::Off
+            && timer.pomodoro_phase == PomodoroPhase::Relax
+            && timer.pomodoro_state != PomodoroState::Stop;
+        // the dimmed screen stays usable, so it takes no input
+        let dim = timer.settings.break_overlay == BreakOverlay::Dim;
+        match (in_break, self.break_overlay) {
+            (true, None) => {
+                let id = window::Id::unique();
+                self.break_overlay = Some(id);
+                get_layer_surface(SctkLayerSurfaceSettings {
+                    id,
+                    layer: Layer::Overlay,
+                    keyboard_interactivity: if dim { KeyboardInteractivity::None } else { KeyboardInteractivity::OnDemand },
+                    pointer_interactivity: !dim,
+                    anchor: Anchor::TOP | Anchor::BOTTOM | Anchor::LEFT | Anchor::RIGHT,
+                    output: IcedOutput::Active,
+                    namespace: "cosmic-pomodoro-break".into(),
+                    size: Some((None, None)),
+                    exclusive_zone: -1,
+                    size_limits: Limits::NONE.min_width(1.0).min_height(1.0),
+                    ..Default::default()
+                })
+            }
+            (false, Some(id)) => {
+                self.break_overlay = None;
+                destroy_layer_surface(id)
+            }
+            _ => Command::none(),
+        }
+    }
+    #[cfg(not(feature = "wayland"))]
+    fn sync_break_overlay(&mut self) -> Command<Message> {
+        Command::none()
+    }
+    /// Persists the timers, so the next launch continues with them.
+    fn save_timers(&mut self) {
+        let now = Local::now();
+        self.config.saved_timers = self.timers.iter().map(|timer| timer.snapshot(now)).collect();
+        self.save_config();
+    }
+    fn write_state_file(&mut self) {
+        if !self.config.write_state_file {
+            return;
+        }
+        let timer = self.timer();
+        let result = state_file::write(
+            timer.pomodoro_phase,
+            timer.pomodoro_state,
+            timer.remaining_sec.load(Ordering::SeqCst),
+            timer.initial_sec,
+        );
+        if let Err(error) = result {
+            tracing::warn!(%error, "failed to write the state file");
+        }
+        self.state_file_written = Some(Instant::now());
+    }
+    fn save_config(&self) {
+        if let Some(config_handler) = &self.config_handler {
+            _ = self.config.write_entry(config_handler);
+        }
+    }
+    /// Whether `message` would skip a break or change a phase's length against the purist mode.
+    fn is_against_purist(&self, message: &Message) -> bool {
+        let timer = self.timer();
+        match message {
+            Message::SkipPhase => timer.keeps_break(),
+            Message::BankAndBreak | Message::QuickStart(_) | Message::AddOneOff | Message::AdjustUpcomingLength(_) => timer.settings.purist,
+            _ => false,
+        }
+    }
+    /// Adds the focus time of the records a timer's history got since it had `recorded` records.
+    /// Interrupted focus periods only count if the statistics count partial focus time.
+    fn add_focus_time(&mut self, index: usize, recorded: usize) {
+        let count_partial = self.config.stats_count_partial;
+        let focus_secs: u32 = self.timers[index].history.records[recorded..].iter()
+            .filter(|record| record.phase == PomodoroPhase::Focus && (record.completed || count_partial))
+            .map(|record| record.actual_secs)
+            .sum();
+        let overtime_secs: u32 = self.timers[index].history.records[recorded..].iter()
+            .map(|record| record.overtime_secs)
+            .sum();
+        if focus_secs > 0 {
+            *self.config.daily_focus_secs.entry(Local::now().date_naive()).or_default() += focus_secs;
+        }
+        if overtime_secs > 0 {
+            *self.config.daily_overtime_secs.entry(Local::now().date_naive()).or_default() += overtime_secs;
+        }
+        if focus_secs > 0 || overtime_secs > 0 {
+            self.save_config();
+        }
+    }
+    fn count_completed_focus(&mut self) {
+        let today = Local::now().date_naive();
+        let completed = self.config.daily_completed.entry(today).or_default();
+        *completed += 1;
+        let goal_reached = *completed == self.config.daily_goal;
+        let longest_streak = statistics::longest_streak(&self.config.daily_completed, self.config.daily_goal);
+        self.config.longest_streak = self.config.longest_streak.max(longest_streak);
+        self.save_config();
+        if goal_reached && self.config.summary_on_goal {
+            self.send_summary(today);
+        }
+    }
+    /// Notifies about the totals of `day`, once per day.
+    /// The totals are kept per day, so they start over at local midnight.
+    fn send_summary(&mut self, day: NaiveDate) {
+        if self.config.summary_sent_on == Some(day) {
+            return;
+        }
+        self.config.summary_sent_on = Some(day);
+        self.save_config();
+        if self.presentation_mode {
+            return;
+        }
+        let count = self.config.daily_completed.get(&day).copied().unwrap_or(0);
+        let focus = Duration::from_secs(self.config.daily_focus_secs.get(&day).copied().unwrap_or(0) as u64);
+        let body = fl!("summary", "notification",
+            count = count,
+            hours = focus.as_minutes() / 60,
+            minutes = focus.as_minutes() % 60);
+        let mut notification = Notification::new();
+        notification.summary(&fl!("summary"))
+            .body(&body);
+        self.show_notification(&notification);
+    }
+    fn timer(&self) -> &PomodoroTimer {
+        &self.timers[self.active_timer]
+    }
+    fn timer_mut(&mut self) -> &mut PomodoroTimer {
+        &mut self.timers[self.active_timer]
+    }
+    fn select_timer(&mut self, index: usize) {
+        self.active_timer = index;
+        // the undo stash refers to the intervals of the previous timer
+        self.deleted_interval = None;
+    }
+    fn get_timer_tabs(&self) -> Element<Message> {
+        let mut tabs = widget::row::with_capacity(self.timers.len() + 1).spacing(8);
+        for index in 0..self.timers.len() {
+            let label = fl!("timer-tab", index = index + 1);
+            let tab = if index == self.active_timer {
+                widget::button::suggested(label)
+            } else {
+                widget::button::standard(label)
+            };
+            tabs = tabs.push(tab.on_press(Message::SelectTimer(index)));
+        }
+        tabs = tabs.push(widget::button::text(fl!("close-timer"))
+            .on_press(Message::CloseTimer(self.active_timer)));
+        tabs.apply(widget::container)
+            .width(Length::Fill)
+            .align_x(Horizontal::Center)
+            .into()
+    }
+    /// A button with a small bundled icon in front of its label, disabled without a message.
+    fn get_icon_button(icon_name: &'static str, label: String, message: Option<Message>) -> Element<'static, Message> {
+        let icon = widget::svg(icon_cache::get_icon_cache_handle(icon_name))
+            .width(Length::Fixed(20.0))
+            .height(Length::Fixed(20.0));
+        widget::button(widget::row::with_capacity(2)
+            .push(icon)
+            .push(widget::text::text(label))
+            .align_items(Alignment::Center)
+            .spacing(8))
+            .style(cosmic::style::Button::Standard)
+            .on_press_maybe(message)
+            .into()
+    }
+    /// The key bound to `action`, as shown below its button, `None` if there is none or the hints are off.
+    /// Of several keys the one sorting first is shown, so it doesn't change between redraws.
+    fn shortcut_hint(&self, action: MenuAction) -> Option<String> {
+        if !self.config.shortcut_hints {
+            return None;
+        }
+        self.key_binds.iter()
+            .filter(|(_, bound)| **bound == action)
+            .map(|(key_bind, _)| key_bind.to_string())
+            .min()
+    }
+    /// One dot per interval, filled for each focus period completed in this cycle.
+    fn get_progress_dots(&self) -> Element<Message> {
+        let completed = self.timer().completed_in_cycle();
+        let dots = (0..self.timer().pomodoro_lengths.len())
+            .map(|index| {
+                let name = if index < completed { "dot-filled" } else { "dot-hollow" };
+                widget::svg(icon_cache::get_icon_cache_handle(name))
+                    .width(Length::Fixed(16.0))
+                    .height(Length::Fixed(16.0))
+                    .into()
+            })
+            .collect();
+        widget::row::with_children(dots)
+            .spacing(8)
+            .apply(widget::container)
+            .width(Length::Fill)
+            .align_x(Horizontal::Center)
+            .into()
+    }
+    fn get_schedule_tooltip(&self) -> String {
+        let mut lines = vec![fl!("schedule")];
+        let continuous_focus = self.timer().settings.continuous_focus;
+        for (index, length) in self.timer().pomodoro_lengths.iter().enumerate() {
+            let focus = localization::localize_digits(&Duration::from_secs(length.focus as u64).to_timer_string());
+            if continuous_focus {
+                lines.push(fl!("schedule-entry", "focus-only", index = index + 1, focus = focus));
+                continue;
+            }
+            let relax = localization::localize_digits(&Duration::from_secs(length.relax as u64).to_timer_string());
+            lines.push(fl!("schedule-entry", index = index + 1, focus = focus, relax = relax));
+        }
+        lines.join("\n")
+    }
+    /// Pauses running focus periods while the window is unfocused, for the timers that have it turned on.
+    /// Breaks keep running, so starting a break right away on a focused window isn't affected.
+    /// The timers are paused once the window stayed unfocused for their grace period, so glancing at something
+    /// else doesn't pause them, and resumed as soon as it's focused again.
+    /// Returns the grace periods to check the timers after.
+    fn pause_on_unfocus(&mut self, focused: bool) -> Vec<Duration> {
+        if focused {
+            self.unfocused_since = None;
+            for id in std::mem::take(&mut self.paused_on_unfocus) {
+                // a timer changed in the meantime stays as it is
+                if let Some(timer) = self.timers.iter_mut().find(|timer| timer.id == id) {
+                    if timer.pomodoro_state == PomodoroState::Pause && timer.pomodoro_phase == PomodoroPhase::Focus {
+                        timer.resume();
+                    }
+                }
+            }
+            return Vec::new();
+        }
+        // another unfocus event without being focused in between doesn't start the grace period over
+        self.unfocused_since.get_or_insert_with(Instant::now);
+        let mut graces: Vec<Duration> = self.timers.iter()
+            .filter(|timer| timer.settings.pause_on_unfocus)
+            .map(|timer| Duration::from_secs(timer.settings.unfocus_grace_secs as u64))
+            .collect();
+        graces.sort();
+        graces.dedup();
+        graces
+    }
+    /// Pauses the running focus periods whose grace period ran out since the window lost focus.
+    fn pause_unfocused_timers(&mut self) {
+        let Some(unfocused_since) = self.unfocused_since else {
+            return;
+        };
+        let unfocused = unfocused_since.elapsed();
+        for timer in &mut self.timers {
+            if timer.settings.pause_on_unfocus
+                && unfocused >= Duration::from_secs(timer.settings.unfocus_grace_secs as u64)
+                && timer.pomodoro_state == PomodoroState::Run
+                && timer.pomodoro_phase == PomodoroPhase::Focus {
+                timer.pause();
+                self.paused_on_unfocus.push(timer.id);
+            }
+        }
+    }
+    fn is_horizontal_layout(&self) -> bool {
+        match self.timer().settings.layout {
+            Layout::Vertical => false,
+            Layout::Horizontal => true,
+            Layout::Automatic => self.window_size.is_some_and(|(width, height)| width > height),
+        }
+    }
     fn is_focused(&self) -> bool {
         match self.core.focused_window() {
             Some(_) => true,
@@ -430,3 +2260,348 @@ impl CosmicPomodoro {
         }
     }
 }
+
+fn restore_timers(saved_timers: &[TimerSnapshot], restore_paused: bool, position_only: bool) -> Vec<PomodoroTimer> {
+    let now = Local::now();
+    let timers = saved_timers.iter()
+        .map(|snapshot| {
+            let mut timer = PomodoroTimer::new();
+            timer.restore_settings(&snapshot.settings);
+            if position_only {
+                timer.restore_position(snapshot);
+            } else {
+                timer.restore(snapshot, restore_paused, now);
+            }
+            timer
+        })
+        .collect::<Vec<_>>();
+    if timers.is_empty() {
+        vec![PomodoroTimer::new()]
+    } else {
+        timers
+    }
+}
+
+/// Draws the progress of a phase into the `progress-circle` arc of an icon, in `ring_color` instead of the icon's color if set.
+/// A `filled` arc is closed to the center and filled like a pie.
+/// `None` if the icon isn't valid SVG. An icon without the arc, or drawn on another canvas than the bundled icons,
+/// is returned unchanged.
+fn draw_progress_ring(icon_svg: &[u8], percentage: f32, radius: f32, stroke_width: f32, ring_color: Option<&str>, filled: bool) -> Option<Vec<u8>> {
+    let radian = 2.0 * std::f32::consts::PI * percentage;
+    let content = str::from_utf8(icon_svg).ok()?;
+    let mut reader = Reader::from_str(content);
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    let mut view_box = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"svg" => {
+                view_box = e.try_get_attribute("viewBox").ok().flatten().map(|attr| attr.value.into_owned());
+                writer.write_event(Event::Start(e)).ok()?;
+            }
+            Ok(Event::Empty(e)) if e.attributes().any(|attr|
+                {
+                    if !attr.is_ok() {
+                        return false;
+                    }
+                    let attr = attr.unwrap();
+                    attr.key.local_name().as_ref() == b"id" && attr.value.as_ref() == b"progress-circle"
+                }) => {
+
+                let mut elem = BytesStart::new("path");
+
+                // collect existing attributes except d and stroke-width, and the color if it's replaced
+                elem.extend_attributes(e.attributes()
+                    .filter_map(Result::ok)
+                    .filter(|attr| attr.key.local_name().as_ref() != b"d"
+                        && attr.key.local_name().as_ref() != b"stroke-width")
+                    .filter(|attr| ring_color.is_none()
+                        || (attr.key.local_name().as_ref() != b"style" && attr.key.local_name().as_ref() != b"stroke"))
+                    .filter(|attr| !filled || attr.key.local_name().as_ref() != b"fill")
+                );
+                let stroke_width = stroke_width.to_string();
+                elem.push_attribute(("stroke-width", stroke_width.as_str()));
+                if let Some(ring_color) = ring_color {
+                    elem.push_attribute(("stroke", ring_color));
+                }
+                if filled {
+                    elem.push_attribute(("fill", ring_color.unwrap_or(PIE_FILL_COLOR)));
+                }
+
+                let Ok(Some(data)) = e.try_get_attribute("d") else {
+                    // a custom icon may draw its ring differently, it's kept as it is then
+                    writer.write_event(Event::Empty(e.borrow())).ok()?;
+                    continue;
+                };
+                let data_string = str::from_utf8(data.value.as_ref()).ok()?;
+                let mut parts = data_string.split(' ').collect::<Vec<_>>();
+
+                // the arc is redrawn around the center of the bundled icons, starting with its move to the start
+                let bundled = parts.first() == Some(&"M") && view_box.as_deref() == Some(BUNDLED_VIEW_BOX.as_bytes());
+                let a_position = parts.iter().position(|&part| part.eq("A"));
+                let Some(a_position) = a_position.filter(|&a_position| bundled && parts.len() > a_position + 7) else {
+                    writer.write_event(Event::Empty(e.borrow())).ok()?;
+                    continue;
+                };
+                // the arc starts at the right of the circle, see the rotation of the path
+                let start_x = (260.0 + radius).to_string();
+                parts[1] = &start_x;
+                let radius_string = radius.to_string();
+                parts[a_position + 1] = &radius_string;
+                parts[a_position + 2] = &radius_string;
+                let large_arc_postion = a_position + 4;
+                let x_position = a_position + 6;
+                let y_position = a_position + 7;
+                if percentage > 0.5 {
+                    parts[large_arc_postion] = "1";
+                } else {
+                    parts[large_arc_postion] = "0";
+                }
+                let x = (260.0 + radian.cos() * radius).to_string();
+                parts[x_position] = &x;
+                let y = (260.0 + radian.sin() * radius).to_string();
+                parts[y_position] = &y;
+                let path = if filled {
+                    format!("M 260 260 L {start_x} 260 A {radius_string} {radius_string} 0 {} 1 {x} {y} Z", parts[large_arc_postion])
+                } else {
+                    parts.join(" ")
+                };
+                elem.push_attribute(("d", path.as_str()));
+                // writes the event to the writer
+                writer.write_event(Event::Empty(elem)).ok()?;
+            }
+            Ok(Event::Eof) => break,
+            // we can either move or borrow the event to write, depending on your use-case
+            Ok(e) => writer.write_event(e).ok()?,
+            Err(_) => return None,
+        }
+    }
+    Some(writer.into_inner().into_inner())
+}
+
+/// The text color with the most contrast to the background of the theme.
+fn high_contrast_text_color() -> Color {
+    if theme::active().cosmic().is_dark {
+        Color::WHITE
+    } else {
+        Color::BLACK
+    }
+}
+
+/// How much of the phase is done, between 0 and 1, for all progress styles.
+fn progress_percentage(initial_secs: u32, remaining_secs: u32, settings: &Settings) -> f32 {
+    // nothing runs before a phase, and a remaining time above the initial one would overshoot the circle
+    let percentage = if initial_secs == 0 {
+        0.0
+    } else {
+        (1.0 - remaining_secs as f32 / initial_secs as f32).clamp(0.0, 1.0)
+    };
+    // only the drawn progress is eased, the countdown stays linear
+    if settings.ease_ring { ease_out(percentage) } else { percentage }
+}
+
+/// Cubic ease-out of a progress between 0 and 1, fast at first and slowing down towards the end.
+fn ease_out(progress: f32) -> f32 {
+    1.0 - (1.0 - progress).powi(3)
+}
+
+fn default_summary_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(17, 0, 0).unwrap()
+}
+
+/// Forwards the events of a timer's countdown thread.
+fn timer_subscription(timer: &PomodoroTimer) -> Subscription<Message> {
+    let id = timer.id;
+    let events = timer.events.clone();
+    subscription::channel((TypeId::of::<TimerEvent>(), id), 10, move |mut output| async move {
+        let mut events = events.lock().await;
+        loop {
+            match events.recv().await {
+                Some(TimerEvent::Elapsed) => {
+                    if let Err(error) = output.send(Message::TimerElapsed(id)).await {
+                        tracing::error!(timer = id, %error, "failed to forward the end of a phase");
+                    }
+                }
+                // the timer is gone, so is this subscription shortly
+                None => std::future::pending::<()>().await,
+            }
+        }
+    })
+}
+
+/// The remaining time, or the time worked past the end in overtime, as precise as `granularity`.
+fn remaining_label(granularity: DisplayGranularity, remaining: Duration, overtime: Option<Duration>) -> String {
+    match (granularity, overtime) {
+        (DisplayGranularity::Seconds, Some(overtime)) => format!("+{}", localization::localize_digits(&overtime.to_timer_string())),
+        (DisplayGranularity::Minutes, Some(overtime)) => format!("+{}", fl!("remaining-minutes", minutes = overtime.as_secs() / 60)),
+        (DisplayGranularity::Seconds, None) => localization::localize_digits(&remaining.to_timer_string()),
+        (DisplayGranularity::Minutes, None) => fl!("remaining-minutes", minutes = remaining.as_minutes_ceil()),
+    }
+}
+
+fn bound_menu_action(action: BindableAction) -> MenuAction {
+    match action {
+        BindableAction::StartPause => MenuAction::StartTimer,
+        BindableAction::Skip => MenuAction::SkipPhase,
+        BindableAction::Reset => MenuAction::ResetTimer,
+    }
+}
+
+/// `control` with the key bound to it in small print below, see [`CosmicPomodoro::shortcut_hint`].
+fn with_hint<'a>(control: impl Into<Element<'a, Message>>, hint: Option<String>) -> widget::Column<'a, Message> {
+    let mut column = widget::column::with_capacity(2)
+        .push(control)
+        .align_items(Alignment::Center)
+        .spacing(4);
+    if let Some(hint) = hint {
+        column = column.push(widget::text::caption(hint));
+    }
+    column
+}
+
+fn key_binds(key_bindings: &KeyBindings) -> HashMap<KeyBind, MenuAction> {
+    let mut key_binds = HashMap::new();
+
+    macro_rules! bind {
+        ([$($modifier:ident),*], $key:expr, $action:expr) => {{
+            key_binds.insert(
+                KeyBind {
+                    modifiers: vec![$(menu::key_bind::Modifier::$modifier),*],
+                    key: $key,
+                },
+                $action,
+            );
+        }};
+    }
+
+    bind!([], Key::Character("1".into()), MenuAction::QuickStart(15 * 60));
+    bind!([], Key::Character("2".into()), MenuAction::QuickStart(25 * 60));
+    bind!([], Key::Character("5".into()), MenuAction::QuickStart(50 * 60));
+    bind!([], Key::Named(keyboard::key::Named::ArrowUp), MenuAction::AdjustUpcomingLength(ADJUST_STEP_SECS));
+    bind!([], Key::Named(keyboard::key::Named::ArrowDown), MenuAction::AdjustUpcomingLength(-ADJUST_STEP_SECS));
+    // shown next to the menu item by `menu::items`
+    bind!([Ctrl], Key::Character(",".into()), MenuAction::Settings);
+
+    for (&action, stored) in key_bindings {
+        if let Some(key_bind) = stored.to_key_bind() {
+            // the fixed bindings above take precedence
+            key_binds.entry(key_bind).or_insert(bound_menu_action(action));
+        }
+    }
+
+    key_binds
+}
+
+/// Asks for a destination and writes the card drawn as a PNG image there.
+async fn export_summary_card(card: SummaryCard) -> Result<(), String> {
+    let file = rfd::AsyncFileDialog::new()
+        .set_file_name(format!("pomodoro-{}.png", card.day))
+        .add_filter("PNG", &["png"])
+        .save_file()
+        .await;
+    // the dialog was cancelled
+    let Some(file) = file else {
+        return Ok(());
+    };
+    // loading the fonts and drawing would block the UI
+    let png = tokio::task::spawn_blocking(move || card.to_png())
+        .await
+        .map_err(|e| e.to_string())??;
+    tokio::fs::write(file.path(), png).await.map_err(|e| e.to_string())
+}
+
+/// Asks for a destination and writes the exported history there.
+async fn export_history(csv: String) -> Result<(), String> {
+    let file = rfd::AsyncFileDialog::new()
+        .set_file_name("pomodoro-history.csv")
+        .add_filter("CSV", &["csv"])
+        .save_file()
+        .await;
+    match file {
+        Some(file) => tokio::fs::write(file.path(), csv).await.map_err(|e| e.to_string()),
+        // the dialog was cancelled
+        None => Ok(()),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::settings::StartPhase;
+
+    #[test]
+    fn progress_before_a_phase_is_zero() {
+        let settings = Settings::new();
+        assert_eq!(progress_percentage(0, 0, &settings), 0.0);
+        assert_eq!(progress_percentage(0, 30, &settings), 0.0);
+    }
+
+    #[test]
+    fn progress_stays_within_the_circle() {
+        let settings = Settings::new();
+        assert_eq!(progress_percentage(60, 90, &settings), 0.0);
+        assert_eq!(progress_percentage(60, 30, &settings), 0.5);
+        assert_eq!(progress_percentage(60, 0, &settings), 1.0);
+    }
+
+    /// The bundled icon, a user's icon of the same name would take its place in the icon cache.
+    const PLAY_SVG: &[u8] = include_bytes!("../res/icons/play.svg");
+
+    /// The `d` attribute of the progress ring in an icon.
+    fn ring_data(svg: &[u8]) -> String {
+        let mut reader = Reader::from_reader(svg);
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Empty(e) if e.try_get_attribute("id").unwrap().is_some_and(|id| id.value.as_ref() == b"progress-circle") => {
+                    return String::from_utf8(e.try_get_attribute("d").unwrap().unwrap().value.into_owned()).unwrap();
+                }
+                Event::Eof => panic!("no progress ring in the icon"),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn progress_ring_has_no_nan() {
+        for (initial_secs, remaining_secs) in [(0, 0), (60, 0), (60, 60), (60, 90)] {
+            let percentage = progress_percentage(initial_secs, remaining_secs, &Settings::new());
+            for filled in [false, true] {
+                let svg = draw_progress_ring(PLAY_SVG, percentage, 200.0, 10.0, None, filled).unwrap();
+                let data = ring_data(&svg);
+                assert!(!data.contains("NaN"));
+                // the arc was drawn with the given radius
+                let start = if filled { "M 260 260 L 460 260 A 200 200 " } else { "M 460 260 A 200 200 " };
+                assert!(data.starts_with(start), "{data}");
+                assert_ne!(data, ring_data(PLAY_SVG));
+            }
+        }
+    }
+
+    #[test]
+    fn progress_ring_keeps_other_icons() {
+        // another canvas than the bundled icons
+        let icon = String::from_utf8(PLAY_SVG.to_vec()).unwrap().replace("0 0 520 520", "0 0 24 24");
+        let svg = draw_progress_ring(icon.as_bytes(), 0.5, 200.0, 10.0, None, false).unwrap();
+        assert_eq!(ring_data(&svg), ring_data(PLAY_SVG));
+        // a path not starting with a move
+        let original = ring_data(PLAY_SVG);
+        let icon = String::from_utf8(PLAY_SVG.to_vec()).unwrap().replace(&original, &original.replacen("M ", "L ", 1));
+        let svg = draw_progress_ring(icon.as_bytes(), 0.5, 200.0, 10.0, None, false).unwrap();
+        assert_eq!(ring_data(&svg), original.replacen("M ", "L ", 1));
+    }
+
+    #[test]
+    fn restores_the_start_phase() {
+        let mut saved = PomodoroTimer::new();
+        saved.settings.start_phase = StartPhase::Relax;
+        saved.reset();
+        let mut timers = restore_timers(&[saved.snapshot(Local::now())], false, false);
+        let timer = &mut timers[0];
+        let long_break = timer.pomodoro_lengths.last().unwrap().relax;
+        assert_eq!(timer.settings.start_phase, StartPhase::Relax);
+        assert_eq!(timer.pomodoro_phase, PomodoroPhase::BeforeRelax);
+        assert_eq!(timer.remaining_sec.load(Ordering::SeqCst), long_break);
+        // a reset starts with the break again
+        timer.reset();
+        assert_eq!(timer.pomodoro_phase, PomodoroPhase::BeforeRelax);
+        assert_eq!(timer.remaining_sec.load(Ordering::SeqCst), long_break);
+    }
+}
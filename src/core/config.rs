@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
+use chrono::{NaiveDate, NaiveTime};
+use crate::core::key_bindings::{default_key_bindings, KeyBindings};
+use crate::core::pomodoro_timer::TimerSnapshot;
+use crate::views::settings::NotificationMode;
+use crate::core::statistics::DailyCounts;
+
+/// Persisted application state, stored with cosmic-config under the app id.
+#[derive(Clone, CosmicConfigEntry, Debug, Eq, PartialEq)]
+#[version = 1]
+pub struct Config {
+    /// Whether the first-run hint was dismissed. It stays unset until then,
+    /// so a missing config shows the hint.
+    pub hint_dismissed: bool,
+    /// Completed focus periods per day needed to continue a streak, 0 to turn streaks off.
+    pub daily_goal: u32,
+    pub daily_completed: DailyCounts,
+    /// Seconds spent focusing per day, see `stats_count_partial`.
+    pub daily_focus_secs: DailyCounts,
+    /// Interruptions of focus periods per day.
+    pub daily_interruptions: DailyCounts,
+    /// Seconds worked past the end of focus periods per day, apart from `daily_focus_secs`.
+    pub daily_overtime_secs: DailyCounts,
+    /// Whether the time of interrupted focus periods counts towards `daily_focus_secs`.
+    /// Off by default, so only fully completed pomodoros count.
+    pub stats_count_partial: bool,
+    /// Counts a completed focus period in `daily_completed` once its break is over rather than when it ends.
+    /// A skipped break wasn't over, so its pomodoro isn't counted, without breaks it's counted when it ends.
+    pub count_after_break: bool,
+    pub longest_streak: u32,
+    /// Time of day to send the summary of the day, `None` to not send it at a fixed time.
+    pub summary_at: Option<NaiveTime>,
+    /// Sends the summary of the day once the daily goal is reached.
+    pub summary_on_goal: bool,
+    /// The last day a summary was sent, so it is sent at most once a day.
+    pub summary_sent_on: Option<NaiveDate>,
+    pub key_bindings: KeyBindings,
+    /// The timers as they were when last changed, restored on the next launch.
+    pub saved_timers: Vec<TimerSnapshot>,
+    /// Restores a running timer paused instead of counting the time the app was closed.
+    pub restore_paused: bool,
+    /// Restores only the interval the timers were at, ready to start its focus period,
+    /// instead of continuing the phase that was left, see `PomodoroTimer::restore_position`.
+    pub restore_position_only: bool,
+    /// Keeps the timers running without a window when it is closed.
+    pub run_in_background: bool,
+    pub notification_mode: NotificationMode,
+    /// Notifies when a phase ended and the next one waits to be started.
+    /// Phases that start on their own are notified either way.
+    pub ready_notifications: bool,
+    /// Adds the focus periods done in the cycle to the phase notifications, like `(3/4)`.
+    pub notification_progress: bool,
+    /// Whether the notification that a break starts disappears on its own instead of staying in the notification list.
+    pub transient_break_notifications: bool,
+    /// Like `transient_break_notifications`, for the notification that a focus period starts.
+    pub transient_focus_notifications: bool,
+    /// Counts the session time on the wall clock, instead of only while a timer is running.
+    pub session_time_wall_clock: bool,
+    /// Activities to pick one at random from for the notification that a break starts.
+    pub break_activities: Vec<String>,
+    /// Writes the state of the active timer to a file for status bars, see `state_file`.
+    pub write_state_file: bool,
+    /// Port on localhost of the status server, 0 to turn it off, see `http_status`.
+    /// Only used if the app is built with the `http-status` feature.
+    pub http_status_port: u16,
+    /// Resizes the window to what the view needs, until it's resized by hand.
+    pub fit_window_to_content: bool,
+    /// Milliseconds between redraws of a running countdown.
+    /// Shorter is smoother, especially the progress ring, longer wakes the CPU up less often and saves battery.
+    pub refresh_interval_ms: u32,
+    /// Draws the progress ring and the remaining time with the most contrast, `None` to follow the system theme.
+    pub high_contrast: Option<bool>,
+    /// Shows the keys bound to the start, skip and reset buttons below them.
+    pub shortcut_hints: bool,
+    /// Shows the remaining time in the window title, for the taskbar and the overview while the window is minimized.
+    pub countdown_in_title: bool,
+    /// Starts the cycle of the timers over on the first check of a new day, see `cycle_day`.
+    pub reset_daily: bool,
+    /// The day a timer was last started on, so the cycle started that day.
+    pub cycle_day: Option<NaiveDate>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            hint_dismissed: false,
+            daily_goal: 8,
+            daily_completed: DailyCounts::new(),
+            daily_focus_secs: DailyCounts::new(),
+            daily_interruptions: DailyCounts::new(),
+            daily_overtime_secs: DailyCounts::new(),
+            stats_count_partial: false,
+            count_after_break: false,
+            longest_streak: 0,
+            summary_at: None,
+            summary_on_goal: false,
+            summary_sent_on: None,
+            key_bindings: default_key_bindings(),
+            saved_timers: Vec::new(),
+            restore_paused: false,
+            restore_position_only: false,
+            run_in_background: false,
+            notification_mode: NotificationMode::Both,
+            ready_notifications: true,
+            notification_progress: true,
+            transient_break_notifications: true,
+            transient_focus_notifications: false,
+            session_time_wall_clock: true,
+            break_activities: Vec::new(),
+            write_state_file: false,
+            http_status_port: 8935,
+            fit_window_to_content: false,
+            refresh_interval_ms: 250,
+            high_contrast: None,
+            shortcut_hints: true,
+            countdown_in_title: true,
+            reset_daily: false,
+            cycle_day: None,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config, falling back to the defaults for missing or invalid entries.
+    /// Invalid ones are logged, e.g. a saved interval of zero seconds, see `duration_string`.
+    pub fn load(app_id: &str) -> (Option<cosmic_config::Config>, Self) {
+        match cosmic_config::Config::new(app_id, Self::VERSION) {
+            Ok(handler) => {
+                let config = match Self::get_entry(&handler) {
+                    Ok(config) => config,
+                    Err((errors, config)) => {
+                        for error in errors {
+                            // entries that were never written are expected
+                            if !matches!(&error, cosmic_config::Error::GetKey(_, error) if error.kind() == std::io::ErrorKind::NotFound) {
+                                tracing::warn!(?error, "invalid config entry, using its default");
+                            }
+                        }
+                        config
+                    }
+                };
+                (Some(handler), config)
+            }
+            Err(_) => (None, Self::default()),
+        }
+    }
+}
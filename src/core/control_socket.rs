@@ -0,0 +1,104 @@
+//! Controls the active timer through a Unix domain socket, for automation beyond D-Bus.
+//!
+//! The socket is `$XDG_RUNTIME_DIR/cosmic-pomodoro/control.sock` and takes one JSON command per line:
+//!
+//! ```json
+//! {"cmd":"start"}
+//! ```
+//!
+//! `cmd` is one of `start`, `pause`, `skip`, `reset` and `status`.
+//! Every line is answered with one line, the state of the timer as in `state_file` for `status`,
+//! `{"ok":true}` for the other commands and `{"error":"…"}` for lines that aren't a command
+//! and for commands a locked focus period doesn't take.
+
+use std::any::TypeId;
+use std::fs;
+use std::path::PathBuf;
+use cosmic::iced::futures::channel::mpsc;
+use cosmic::iced::futures::SinkExt;
+use cosmic::iced::{subscription, Subscription};
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use crate::app::Message;
+use crate::core::reply::Reply;
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(tag = "cmd", rename_all = "kebab-case")]
+pub(crate) enum ControlCommand {
+    /// Starts or resumes the timer, nothing happens if it runs already.
+    Start,
+    /// Pauses a running timer.
+    Pause,
+    Skip,
+    /// Resets the timer without asking.
+    Reset,
+    Status,
+}
+
+/// `None` without a runtime dir, there is no socket then.
+fn path() -> Option<PathBuf> {
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR")?;
+    Some(PathBuf::from(runtime_dir).join("cosmic-pomodoro").join("control.sock"))
+}
+
+/// Listens on the socket and turns the commands into messages.
+pub(crate) fn subscription() -> Subscription<Message> {
+    subscription::channel(TypeId::of::<ControlCommand>(), 10, |output| async move {
+        match bind() {
+            Ok(listener) => loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        tokio::spawn(serve(stream, output.clone()));
+                    }
+                    Err(error) => tracing::warn!(%error, "failed to accept a control connection"),
+                }
+            },
+            Err(error) => {
+                tracing::warn!(%error, "failed to open the control socket");
+                std::future::pending().await
+            }
+        }
+    })
+}
+
+fn bind() -> std::io::Result<UnixListener> {
+    let path = path().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "XDG_RUNTIME_DIR is not set"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // left over if the app didn't exit cleanly, a running instance is the only one anyway
+    _ = fs::remove_file(&path);
+    UnixListener::bind(path)
+}
+
+/// Answers the commands of one connection until it's closed.
+async fn serve(stream: UnixStream, mut output: mpsc::Sender<Message>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let answer = match serde_json::from_str::<ControlCommand>(&line) {
+            Ok(command) => {
+                let (reply, receiver) = Reply::channel();
+                if output.send(Message::Control(command, reply)).await.is_err() {
+                    return;
+                }
+                receiver.await.unwrap_or_else(|_| "{\"error\":\"no answer\"}".to_string())
+            }
+            Err(error) => format!("{{\"error\":{}}}", serde_json::Value::String(error.to_string())),
+        };
+        if writer.write_all(format!("{answer}\n").as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Removes the socket on exit.
+pub(crate) fn remove() {
+    if let Some(path) = path() {
+        _ = fs::remove_file(path);
+    }
+}
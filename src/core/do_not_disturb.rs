@@ -0,0 +1,14 @@
+use cosmic::cosmic_config::{self, ConfigGet};
+
+/// Config of the COSMIC notification daemon, which holds the do-not-disturb toggle.
+const NOTIFICATIONS_CONFIG_ID: &str = "com.system76.CosmicNotifications";
+const NOTIFICATIONS_CONFIG_VERSION: u64 = 1;
+const DO_NOT_DISTURB_KEY: &str = "do_not_disturb";
+
+/// Returns whether the desktop is in do-not-disturb mode.
+/// If the state can't be determined, do-not-disturb is assumed to be off.
+pub(crate) fn is_active() -> bool {
+    cosmic_config::Config::new(NOTIFICATIONS_CONFIG_ID, NOTIFICATIONS_CONFIG_VERSION)
+        .and_then(|config| config.get::<bool>(DO_NOT_DISTURB_KEY))
+        .unwrap_or(false)
+}
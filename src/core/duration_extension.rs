@@ -1,8 +1,19 @@
 use std::time::Duration;
 
+/// Minutes and seconds of a [`Duration`], for showing it.
+/// All values are whole numbers of the unsigned seconds of the duration, so they can't be negative,
+/// and they are derived by division only, so they can't overflow even for `u64::MAX` seconds.
 pub trait TimeDurationExt {
+    /// The total of whole minutes, not limited to an hour: 90 for an hour and a half.
     fn as_minutes(&self) -> u64;
+    /// The seconds component, from 0 to 59: 5 for 2:05.
     fn as_seconds(&self) -> u64;
+    /// The total of minutes, counting a started minute as a full one: 1 for 1 second, 0 for 0.
+    fn as_minutes_ceil(&self) -> u64;
+    /// Total minutes and the seconds component, e.g. `25:00` or `90:00` for an hour and a half.
+    fn to_timer_string(&self) -> String;
+    /// Like `to_timer_string` with hours, e.g. `2:14:05`. The minutes are a component from 0 to 59 then.
+    fn to_hours_string(&self) -> String;
 }
 
 impl TimeDurationExt for Duration {
@@ -13,4 +24,61 @@ impl TimeDurationExt for Duration {
     fn as_seconds(&self) -> u64 {
         self.as_secs() % 60
     }
-}
\ No newline at end of file
+
+    fn as_minutes_ceil(&self) -> u64 {
+        self.as_secs().div_ceil(60)
+    }
+
+    fn to_timer_string(&self) -> String {
+        format!("{:02}:{:02}", self.as_minutes(), self.as_seconds())
+    }
+
+    fn to_hours_string(&self) -> String {
+        format!("{}:{:02}:{:02}", self.as_secs() / 3600, self.as_minutes() % 60, self.as_seconds())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero() {
+        let duration = Duration::ZERO;
+        assert_eq!((duration.as_minutes(), duration.as_seconds(), duration.as_minutes_ceil()), (0, 0, 0));
+        assert_eq!(duration.to_timer_string(), "00:00");
+        assert_eq!(duration.to_hours_string(), "0:00:00");
+    }
+
+    #[test]
+    fn minutes_go_past_an_hour() {
+        let duration = Duration::from_secs(60 * 60);
+        assert_eq!(duration.to_timer_string(), "60:00");
+        assert_eq!(duration.to_hours_string(), "1:00:00");
+        let duration = Duration::from_secs(90 * 60 + 5);
+        assert_eq!((duration.as_minutes(), duration.as_seconds()), (90, 5));
+        assert_eq!(duration.to_hours_string(), "1:30:05");
+    }
+
+    #[test]
+    fn started_minutes_count_as_full_ones() {
+        assert_eq!(Duration::from_secs(1).as_minutes_ceil(), 1);
+        assert_eq!(Duration::from_secs(60).as_minutes_ceil(), 1);
+        assert_eq!(Duration::from_secs(61).as_minutes_ceil(), 2);
+        // the fraction of a second isn't counted
+        assert_eq!(Duration::from_millis(60_900).as_minutes_ceil(), 1);
+    }
+
+    #[test]
+    fn largest_durations_dont_overflow() {
+        let duration = Duration::from_secs(u32::MAX as u64);
+        assert_eq!(duration.to_timer_string(), "71582788:15");
+        let duration = Duration::from_secs(u64::MAX);
+        assert_eq!(duration.as_minutes(), 307_445_734_561_825_860);
+        assert_eq!(duration.as_seconds(), 15);
+        assert_eq!(duration.as_minutes_ceil(), 307_445_734_561_825_861);
+        assert_eq!(duration.to_hours_string(), "5124095576030431:00:15");
+        let duration = Duration::MAX;
+        assert_eq!(duration.as_minutes(), 307_445_734_561_825_860);
+    }
+}
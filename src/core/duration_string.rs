@@ -0,0 +1,161 @@
+//! Durations as written by hand, e.g. `25m`, `1h30m` or `90s`, for the lengths in the config.
+//! Plain numbers of seconds are accepted as well.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+
+/// The lengths a focus period or break can have. A phase of zero seconds would be over right away.
+pub(crate) const LENGTH_RANGE: RangeInclusive<u32> = 1..=8 * 60 * 60;
+
+/// Cuts `secs` down to the longest length, `None` for zero, which can't be used.
+pub(crate) fn clamp_length(secs: u32) -> Option<u32> {
+    (secs > 0).then(|| secs.min(*LENGTH_RANGE.end()))
+}
+
+/// Parses a combination of hours, minutes and seconds like `1h30m` into seconds.
+/// Each unit may appear once, in the order `h`, `m`, `s`. The result must not be zero
+/// and is cut down to the end of `LENGTH_RANGE`.
+pub(crate) fn parse(input: &str) -> Result<u32, String> {
+    let input = input.trim();
+    if let Ok(secs) = input.parse::<u32>() {
+        return to_length(secs, input);
+    }
+    let mut total: u32 = 0;
+    let mut number = String::new();
+    // the units still allowed, so `30m1h` or `5m5m` are rejected
+    let mut units: &[(char, u32)] = &[('h', 3600), ('m', 60), ('s', 1)];
+    for character in input.chars() {
+        if character.is_ascii_digit() {
+            number.push(character);
+            continue;
+        }
+        let Some(index) = units.iter().position(|&(unit, _)| unit == character) else {
+            if "hms".contains(character) {
+                return Err(format!("invalid duration \"{input}\": use h, m and s once each and in this order"));
+            }
+            return Err(format!("invalid duration \"{input}\": unexpected \"{character}\", use h, m and s"));
+        };
+        if number.is_empty() {
+            return Err(format!("invalid duration \"{input}\": missing a number before \"{character}\""));
+        }
+        let value: u32 = number.parse().map_err(|_| format!("invalid duration \"{input}\": too long"))?;
+        total = value.checked_mul(units[index].1)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| format!("invalid duration \"{input}\": too long"))?;
+        number.clear();
+        units = &units[index + 1..];
+    }
+    if !number.is_empty() {
+        return Err(format!("invalid duration \"{input}\": missing a unit after {number}"));
+    }
+    to_length(total, input)
+}
+
+fn to_length(secs: u32, input: &str) -> Result<u32, String> {
+    clamp_length(secs).ok_or_else(|| format!("invalid duration \"{input}\": must not be zero"))
+}
+
+/// Writes seconds in the shortest form `parse` reads back, e.g. `25m` or `1h30m`.
+pub(crate) fn format(secs: u32) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    let mut output = String::new();
+    if hours > 0 {
+        output.push_str(&format!("{hours}h"));
+    }
+    if minutes > 0 {
+        output.push_str(&format!("{minutes}m"));
+    }
+    if seconds > 0 || output.is_empty() {
+        output.push_str(&format!("{seconds}s"));
+    }
+    output
+}
+
+/// For `#[serde(with = "duration_string")]` on seconds.
+pub(crate) fn serialize<S: Serializer>(secs: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format(*secs))
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    deserializer.deserialize_any(DurationVisitor)
+}
+
+struct DurationVisitor;
+
+impl<'de> Visitor<'de> for DurationVisitor {
+    type Value = u32;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a duration like 25m or 1h30m, or a number of seconds")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u32, E> {
+        // cut down like any other long length
+        let secs = u32::try_from(value).unwrap_or(u32::MAX);
+        to_length(secs, &value.to_string()).map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u32, E> {
+        let value = u64::try_from(value).map_err(|_| E::custom(format!("invalid duration {value}: negative")))?;
+        self.visit_u64(value)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u32, E> {
+        parse(value).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_units_and_plain_seconds() {
+        assert_eq!(parse("25m"), Ok(25 * 60));
+        assert_eq!(parse("1h30m"), Ok(90 * 60));
+        assert_eq!(parse("1h5s"), Ok(60 * 60 + 5));
+        assert_eq!(parse(" 90s "), Ok(90));
+        assert_eq!(parse("90"), Ok(90));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        for input in ["", "0", "0m", "30m1h", "5m5m", "5x", "m", "1h30", "-5"] {
+            assert!(parse(input).is_err(), "{input} was accepted");
+        }
+    }
+
+    #[test]
+    fn cuts_long_durations_down() {
+        let longest = *LENGTH_RANGE.end();
+        assert_eq!(parse("9h"), Ok(longest));
+        assert_eq!(parse(&u32::MAX.to_string()), Ok(longest));
+        assert!(parse("99999999999h").is_err());
+    }
+
+    #[test]
+    fn formats_what_it_parses() {
+        assert_eq!(format(25 * 60), "25m");
+        assert_eq!(format(90 * 60), "1h30m");
+        assert_eq!(format(3 * 60 * 60 + 5), "3h5s");
+        assert_eq!(format(0), "0s");
+        for secs in [1, 59, 60, 61, 3599, 3600, 3661, *LENGTH_RANGE.end()] {
+            assert_eq!(parse(&format(secs)), Ok(secs));
+        }
+    }
+
+    #[test]
+    fn deserializes_strings_and_numbers() {
+        use serde::de::value::Error;
+        use serde::de::IntoDeserializer;
+        let from_str = |input: &str| deserialize(IntoDeserializer::<Error>::into_deserializer(input));
+        let from_u64 = |input: u64| deserialize(IntoDeserializer::<Error>::into_deserializer(input));
+        assert_eq!(from_str("1h30m"), Ok(90 * 60));
+        assert_eq!(from_u64(90), Ok(90));
+        assert_eq!(from_u64(u64::MAX), Ok(*LENGTH_RANGE.end()));
+        assert!(from_str("0").is_err());
+        assert!(from_u64(0).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! Serves the state of the active timer over HTTP, for dashboards on the same machine.
+//!
+//! The server listens on `127.0.0.1` at `http_status_port` of the config, 8935 by default, 0 turns it off.
+//! It answers `GET /status` with:
+//!
+//! ```json
+//! {"phase":"focus","state":"run","remaining_secs":1234,"initial_secs":1500}
+//! ```
+//!
+//! The object is the one of `state_file`:
+//! - `phase` is one of `before-focus`, `focus`, `before-relax` and `relax`
+//! - `state` is one of `stop`, `run` and `pause`
+//! - `remaining_secs` are the seconds left of the phase
+//! - `initial_secs` is the planned length of the phase in seconds
+//!
+//! Other paths are answered with `404 Not Found`, other methods with `405 Method Not Allowed`.
+//! The server runs as long as its subscription, so it's shut down with the app or when the port changes.
+
+use std::any::TypeId;
+use cosmic::iced::futures::channel::mpsc;
+use cosmic::iced::futures::SinkExt;
+use cosmic::iced::{subscription, Subscription};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use crate::app::Message;
+use crate::core::reply::Reply;
+
+/// The most of a request that is read, the request line is all that's needed.
+const MAX_REQUEST_LEN: usize = 8 * 1024;
+
+struct HttpStatus;
+
+/// Listens on `port` and asks the app for the status of every request.
+pub(crate) fn subscription(port: u16) -> Subscription<Message> {
+    subscription::channel((TypeId::of::<HttpStatus>(), port), 10, move |output| async move {
+        match TcpListener::bind(("127.0.0.1", port)).await {
+            Ok(listener) => loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        tokio::spawn(serve(stream, output.clone()));
+                    }
+                    Err(error) => tracing::warn!(%error, "failed to accept a status connection"),
+                }
+            },
+            Err(error) => {
+                tracing::warn!(port, %error, "failed to open the status server");
+                std::future::pending().await
+            }
+        }
+    })
+}
+
+/// Answers one request and closes the connection.
+async fn serve(mut stream: TcpStream, mut output: mpsc::Sender<Message>) {
+    let Some(request_line) = read_request_line(&mut stream).await else {
+        return;
+    };
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+    let (status, body) = match (method, path) {
+        ("GET", "/status") => {
+            let (reply, receiver) = Reply::channel();
+            if output.send(Message::HttpStatus(reply)).await.is_err() {
+                return;
+            }
+            match receiver.await {
+                Ok(body) => ("200 OK", body),
+                Err(_) => ("503 Service Unavailable", "{\"error\":\"no answer\"}".to_string()),
+            }
+        }
+        (_, "/status") => ("405 Method Not Allowed", "{\"error\":\"only GET is supported\"}".to_string()),
+        _ => ("404 Not Found", "{\"error\":\"not found\"}".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    if let Err(error) = stream.write_all(response.as_bytes()).await {
+        tracing::warn!(%error, "failed to answer a status request");
+    }
+    _ = stream.shutdown().await;
+}
+
+/// The first line of the request, `None` if the connection closed before it was complete.
+async fn read_request_line(stream: &mut TcpStream) -> Option<String> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.contains(&b'\n') {
+        let read = stream.read(&mut buffer).await.ok()?;
+        if read == 0 || request.len() + read > MAX_REQUEST_LEN {
+            return None;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    let line_end = request.iter().position(|&byte| byte == b'\n')?;
+    Some(String::from_utf8_lossy(&request[..line_end]).trim_end().to_string())
+}
@@ -1,13 +1,23 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
 use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
 use cosmic::widget::svg;
 use rust_embed::Embed;
 
+/// The bundled icons, each replaced by a file of the same name in the user icon directory if there is one.
 pub(crate) struct IconCache {
     svg_cache: HashMap<&'static str, Cow<'static,[u8]>>,
     handle_cache: HashMap<&'static str, svg::Handle>,
+    /// When the user icons in the cache were last modified, `None` for the bundled ones.
+    user_icon_modified: HashMap<&'static str, Option<SystemTime>>,
+    /// When the user icon directory was last looked at for each icon.
+    last_checked: HashMap<&'static str, Instant>,
 }
+/// The icons are drawn on every redraw, a changed icon showing up a moment later is fine.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
 #[derive(Embed)]
 #[folder = "res/icons/"]
 struct Icons;
@@ -29,19 +39,61 @@ impl IconCache {
         bundle!("play");
         bundle!("pause");
         bundle!("stop");
-        Self { svg_cache, handle_cache }
+        bundle!("skip");
+        bundle!("dot-filled");
+        bundle!("dot-hollow");
+        Self { svg_cache, handle_cache, user_icon_modified: HashMap::new(), last_checked: HashMap::new() }
+    }
+
+    /// Loads the user icon if it was added, changed or removed since it was last checked,
+    /// at most once every `CHECK_INTERVAL`.
+    fn refresh(&mut self, name: &'static str) {
+        let now = Instant::now();
+        if self.last_checked.get(name).is_some_and(|checked| now.duration_since(*checked) < CHECK_INTERVAL) {
+            return;
+        }
+        self.last_checked.insert(name, now);
+        let path = user_icon_path(name);
+        let modified = path.as_ref()
+            .and_then(|path| fs::metadata(path).ok())
+            .and_then(|metadata| metadata.modified().ok());
+        if self.user_icon_modified.get(name) == Some(&modified) {
+            return;
+        }
+        self.user_icon_modified.insert(name, modified);
+        let data = match path.filter(|_| modified.is_some()).and_then(|path| fs::read(path).ok()) {
+            Some(data) => Cow::Owned(data),
+            // removed, or it can't be read, e.g. while it's being written
+            None => match Icons::get(&format!("{name}.svg")) {
+                Some(bundled) => bundled.data,
+                None => return,
+            },
+        };
+        self.handle_cache.insert(name, svg::Handle::from_memory(data.clone()));
+        self.svg_cache.insert(name, data);
     }
 
     fn get_handle(&mut self, name: &'static str) -> svg::Handle {
+        self.refresh(name);
         self.handle_cache
             .get(name)
             .unwrap()
             .clone()
     }
     fn get_svg(&mut self, name: &'static str) -> Cow<'static,[u8]> {
+        self.refresh(name);
         self.svg_cache.get(name).unwrap().clone()
     }
 }
+
+/// `$XDG_CONFIG_HOME/cosmic-pomodoro/icons/<name>.svg`, or below `~/.config` without it.
+fn user_icon_path(name: &str) -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("cosmic-pomodoro").join("icons").join(format!("{name}.svg")))
+}
+
 static ICON_CACHE: OnceLock<Mutex<IconCache>> = OnceLock::new();
 pub(crate) fn get_icon_cache_handle(name: &'static str) -> svg::Handle {
     let mut icon_cache = ICON_CACHE
@@ -56,4 +108,4 @@ pub(crate) fn get_icon_cache_svg(name: &'static str) -> Cow<'static,[u8]> {
         .lock()
         .unwrap();
     icon_cache.get_svg(name)
-}
\ No newline at end of file
+}
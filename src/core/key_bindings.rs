@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+use cosmic::iced::keyboard::key::Named;
+use cosmic::iced::keyboard::{Key, Modifiers};
+use cosmic::widget::menu::key_bind::{KeyBind, Modifier};
+use serde::{Deserialize, Serialize};
+use crate::fl;
+
+/// Timer actions the user can bind a key to.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub(crate) enum BindableAction {
+    StartPause,
+    Skip,
+    Reset,
+}
+
+impl BindableAction {
+    pub const ALL: [BindableAction; 3] = [BindableAction::StartPause, BindableAction::Skip, BindableAction::Reset];
+
+    pub fn localized_name(&self) -> String {
+        match self {
+            BindableAction::StartPause => fl!("key-bindings", "start-pause"),
+            BindableAction::Skip => fl!("key-bindings", "skip"),
+            BindableAction::Reset => fl!("key-bindings", "reset"),
+        }
+    }
+}
+
+pub(crate) type KeyBindings = BTreeMap<BindableAction, StoredKeyBind>;
+
+pub(crate) fn default_key_bindings() -> KeyBindings {
+    KeyBindings::from([
+        (BindableAction::StartPause, StoredKeyBind::plain("space")),
+        (BindableAction::Skip, StoredKeyBind::plain("s")),
+        (BindableAction::Reset, StoredKeyBind::plain("r")),
+    ])
+}
+
+/// Named keys that can be bound, with the names they are stored as.
+const NAMED_KEYS: [(Named, &str); 23] = [
+    (Named::Space, "space"),
+    (Named::Enter, "enter"),
+    (Named::Tab, "tab"),
+    (Named::Backspace, "backspace"),
+    (Named::Delete, "delete"),
+    (Named::ArrowUp, "up"),
+    (Named::ArrowDown, "down"),
+    (Named::ArrowLeft, "left"),
+    (Named::ArrowRight, "right"),
+    (Named::Home, "home"),
+    (Named::End, "end"),
+    (Named::F1, "f1"),
+    (Named::F2, "f2"),
+    (Named::F3, "f3"),
+    (Named::F4, "f4"),
+    (Named::F5, "f5"),
+    (Named::F6, "f6"),
+    (Named::F7, "f7"),
+    (Named::F8, "f8"),
+    (Named::F9, "f9"),
+    (Named::F10, "f10"),
+    (Named::F11, "f11"),
+    (Named::F12, "f12"),
+];
+
+/// A [`KeyBind`] in a form that can be persisted in the config.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub(crate) struct StoredKeyBind {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub logo: bool,
+    /// A lowercase character or one of the names in `NAMED_KEYS`.
+    pub key: String,
+}
+
+impl StoredKeyBind {
+    fn plain(key: &str) -> Self {
+        Self {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            logo: false,
+            key: key.to_string(),
+        }
+    }
+
+    /// Captures a key press, `None` for keys that can't be bound, e.g. a modifier on its own.
+    pub fn from_key_press(modifiers: Modifiers, key: &Key) -> Option<Self> {
+        let key = match key {
+            Key::Character(character) => character.to_lowercase(),
+            Key::Named(named) => NAMED_KEYS.iter()
+                .find(|(candidate, _)| candidate == named)
+                .map(|(_, name)| name.to_string())?,
+            Key::Unidentified => return None,
+        };
+        Some(Self {
+            ctrl: modifiers.control(),
+            alt: modifiers.alt(),
+            shift: modifiers.shift(),
+            logo: modifiers.logo(),
+            key,
+        })
+    }
+
+    /// `None` if the stored key is unknown, e.g. after editing the config by hand.
+    pub fn to_key_bind(&self) -> Option<KeyBind> {
+        let key = match NAMED_KEYS.iter().find(|(_, name)| *name == self.key) {
+            Some((named, _)) => Key::Named(*named),
+            None if self.key.chars().count() == 1 => Key::Character(self.key.as_str().into()),
+            None => return None,
+        };
+        let mut modifiers = Vec::new();
+        if self.logo {
+            modifiers.push(Modifier::Super);
+        }
+        if self.ctrl {
+            modifiers.push(Modifier::Ctrl);
+        }
+        if self.alt {
+            modifiers.push(Modifier::Alt);
+        }
+        if self.shift {
+            modifiers.push(Modifier::Shift);
+        }
+        Some(KeyBind { modifiers, key })
+    }
+}
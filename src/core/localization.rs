@@ -4,6 +4,12 @@ use i18n_embed::{
     fluent::{fluent_language_loader, FluentLanguageLoader},
     LanguageLoader,
 };
+use i18n_embed::unic_langid::LanguageIdentifier;
+use chrono::{DateTime, Local, Locale, NaiveDate};
+use fluent::types::FluentNumberOptions;
+use fluent::FluentValue;
+use intl_memoizer::concurrent::IntlLangMemoizer;
+use intl_memoizer::Memoizable;
 use once_cell::sync::Lazy;
 use rust_embed::RustEmbed;
 
@@ -17,10 +23,123 @@ pub static LANGUAGE_LOADER: Lazy<FluentLanguageLoader> = Lazy::new(|| {
     loader
         .load_fallback_language(&Localizations)
         .expect("Error while loading fallback language");
+    // numbers passed to messages still select their plural forms, only how they're written changes
+    loader.with_bundles_mut(|bundle| bundle.set_formatter(Some(format_fluent_number)));
 
     loader
 });
 
+/// How numbers are written in the language `fl!` uses.
+struct NumberFormat {
+    /// Between groups of three digits, e.g. the `,` in `1,000`.
+    group_separator: &'static str,
+    /// The digit for zero, the others follow it in Unicode.
+    zero: char,
+}
+
+impl NumberFormat {
+    /// The format of `language`, the one of English for languages not listed.
+    fn of(language: &LanguageIdentifier) -> Self {
+        let (group_separator, zero) = match language.language.as_str() {
+            "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" | "el" => (".", '0'),
+            "fr" | "nb" | "nn" | "sv" | "fi" | "cs" | "sk" | "pl" | "ru" | "uk" | "hu" => ("\u{202F}", '0'),
+            "ar" => ("\u{066C}", '\u{0660}'),
+            "fa" => ("\u{066C}", '\u{06F0}'),
+            "bn" => (",", '\u{09E6}'),
+            "mr" | "ne" => (",", '\u{0966}'),
+            _ => (",", '0'),
+        };
+        Self { group_separator, zero }
+    }
+
+    fn localize_digits(&self, text: &str) -> String {
+        if self.zero == '0' {
+            return text.to_string();
+        }
+        text.chars()
+            .map(|character| match character.to_digit(10) {
+                Some(digit) if character.is_ascii_digit() => char::from_u32(self.zero as u32 + digit).unwrap_or(character),
+                _ => character,
+            })
+            .collect()
+    }
+
+    fn format(&self, number: u64) -> String {
+        let digits = number.to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 * self.group_separator.len());
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                grouped.push_str(self.group_separator);
+            }
+            grouped.push(digit);
+        }
+        self.localize_digits(&grouped)
+    }
+}
+
+/// Lets each Fluent bundle keep the format of its language.
+impl Memoizable for NumberFormat {
+    type Args = ();
+    type Error = ();
+
+    fn construct(language: LanguageIdentifier, _args: ()) -> Result<Self, ()> {
+        Ok(Self::of(&language))
+    }
+}
+
+fn number_format() -> NumberFormat {
+    NumberFormat::of(&LANGUAGE_LOADER.current_language())
+}
+
+/// Writes the ASCII digits of `text` as the digits of the language, e.g. of a `25:00` timer.
+pub fn localize_digits(text: &str) -> String {
+    number_format().localize_digits(text)
+}
+
+/// Writes `number` with the group separators and digits of the language, e.g. `12,345`.
+pub fn format_number(number: u64) -> String {
+    number_format().format(number)
+}
+
+/// Writes the whole numbers passed to messages like [`format_number`], in the language of the bundle.
+/// Numbers with options set in the message, e.g. fraction digits, are left to Fluent.
+fn format_fluent_number(value: &FluentValue, memoizer: &IntlLangMemoizer) -> Option<String> {
+    let FluentValue::Number(number) = value else {
+        return None;
+    };
+    let whole = number.value >= 0.0 && number.value.fract() == 0.0 && number.value <= u64::MAX as f64;
+    if !whole || number.options != FluentNumberOptions::default() {
+        return None;
+    }
+    memoizer.with_try_get::<NumberFormat, _, _>((), |format| format.format(number.value as u64)).ok()
+}
+
+/// The locale for times from `LC_ALL`, `LC_TIME` or `LANG`, e.g. `de_DE.UTF-8`.
+fn time_locale() -> Locale {
+    ["LC_ALL", "LC_TIME", "LANG"].iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| {
+            // without the encoding and modifier
+            let name = value.split(['.', '@']).next().unwrap_or_default().to_string();
+            Locale::try_from(name.as_str()).ok()
+        })
+        .unwrap_or(Locale::POSIX)
+}
+
+/// Hours and minutes of `time`, with AM/PM in locales that use it, e.g. `4:35 PM` or `16:35`.
+pub fn format_time_of_day(time: DateTime<Local>) -> String {
+    let locale = time_locale();
+    let uses_am_pm = !time.format_localized("%p", locale).to_string().is_empty();
+    let format = if uses_am_pm { "%-I:%M %p" } else { "%H:%M" };
+    time.format_localized(format, locale).to_string()
+}
+
+/// `day` in the date format of the locale, e.g. `10/14/26` or `14.10.2026`.
+pub fn format_date(day: NaiveDate) -> String {
+    day.format_localized("%x", time_locale()).to_string()
+}
+
 #[macro_export]
 macro_rules! fl {
     ($message_id:literal) => {{
@@ -31,3 +150,49 @@ macro_rules! fl {
         i18n_embed_fl::fl!($crate::core::localization::LANGUAGE_LOADER, $message_id, $($args), *)
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluent::concurrent::FluentBundle;
+    use fluent::{FluentArgs, FluentResource};
+
+    const STREAK: &str = "streak = { $days ->\n    [one] { $days } day\n   *[other] { $days } days\n}\n";
+
+    fn language(name: &str) -> LanguageIdentifier {
+        name.parse().unwrap()
+    }
+
+    /// The streak message in a bundle of `name` with the number formatter of the app.
+    fn streak(name: &str, days: u64) -> String {
+        let mut bundle = FluentBundle::new_concurrent(vec![language(name)]);
+        bundle.add_resource(FluentResource::try_new(STREAK.to_string()).unwrap()).unwrap();
+        bundle.set_use_isolating(false);
+        bundle.set_formatter(Some(format_fluent_number));
+        let mut args = FluentArgs::new();
+        args.set("days", days);
+        let pattern = bundle.get_message("streak").unwrap().value().unwrap();
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, Some(&args), &mut errors).into_owned();
+        assert!(errors.is_empty(), "{errors:?}");
+        text
+    }
+
+    #[test]
+    fn formats_numbers_in_the_language() {
+        assert_eq!(NumberFormat::of(&language("en")).format(1234567), "1,234,567");
+        assert_eq!(NumberFormat::of(&language("de")).format(1234567), "1.234.567");
+        assert_eq!(NumberFormat::of(&language("ar")).format(1000), "\u{0661}\u{066C}\u{0660}\u{0660}\u{0660}");
+        assert_eq!(NumberFormat::of(&language("de")).format(999), "999");
+        // the bundled English messages
+        assert_eq!(format_number(12345), "12,345");
+    }
+
+    #[test]
+    fn message_numbers_keep_their_plural_forms() {
+        assert_eq!(streak("de", 1), "1 day");
+        assert_eq!(streak("de", 1000), "1.000 days");
+        assert_eq!(streak("ar", 1), "\u{0661} day");
+        assert_eq!(streak("en", 2), "2 days");
+    }
+}
@@ -1,7 +1,24 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 pub mod localization;
+pub mod config;
+#[cfg(feature = "control-socket")]
+pub mod control_socket;
 pub mod duration_extension;
+#[cfg(feature = "http-status")]
+pub mod http_status;
+pub mod duration_string;
+pub mod do_not_disturb;
 pub mod icon_cache;
+pub mod key_bindings;
 pub mod pomodoro_timer;
+#[cfg(any(feature = "control-socket", feature = "http-status"))]
+pub mod reply;
+pub mod session_history;
+pub mod sound;
+pub mod state_file;
+pub mod statistics;
+pub mod summary_card;
+pub mod transition_hook;
+pub mod work_session;
 
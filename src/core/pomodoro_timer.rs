@@ -1,116 +1,1190 @@
+use std::collections::VecDeque;
 use std::sync::{mpsc, Arc};
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::mpsc::Sender;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
 use std::thread;
 use std::thread::sleep;
-use std::time::Duration;
-use crate::views::settings::Settings;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::Mutex;
+use crate::core::duration_string::LENGTH_RANGE;
+use crate::core::session_history::{Distraction, SessionHistory, SessionRecord};
+use crate::fl;
+use crate::views::settings::{LongBreakTrigger, SavedSettings, Settings, SkipBehavior, StartPhase, PURIST_PRESET};
+
+static NEXT_TIMER_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The most focus time that can be banked, see [`PomodoroTimer::bank_and_break`].
+pub const MAX_BANKED_SEC: u32 = 60 * 60;
 
 pub(crate) struct PomodoroTimer {
+    /// Identifies the timer in its events.
+    pub id: usize,
     pub settings: Settings,
     pub pomodoro_lengths: Vec<PomodoroLength>,
     pub position: usize,
+    /// One-off intervals added with [`PomodoroTimer::add_one_off`], each played once after the current interval.
+    /// They aren't part of `pomodoro_lengths`, so they are neither saved nor repeated.
+    one_off_lengths: VecDeque<PomodoroLength>,
+    /// The one-off interval being played, `position` stays on the interval before it.
+    current_one_off: Option<PomodoroLength>,
     pub pomodoro_state: PomodoroState,
     pub pomodoro_phase: PomodoroPhase,
     pub remaining_sec: Arc<AtomicU32>,
+    /// Seconds worked past the end of the focus period, counted while it's kept in overtime.
+    pub overtime_sec: Arc<AtomicU32>,
+    /// Whether the ended focus period keeps running in overtime, see [`Settings::overtime`].
+    in_overtime: bool,
+    /// Whether the current break follows a completed focus period, for counting it once the break is over.
+    pub break_after_completed_focus: bool,
+    /// The planned length of the current phase.
+    pub initial_sec: u32,
+    pub history: SessionHistory,
+    /// What the current focus period is meant for, cleared once it ends.
+    pub note: String,
+    /// Interruptions of the current focus period, recorded with it.
+    pub interruptions: u32,
+    /// When the interruptions of the current focus period happened and why, recorded with it.
+    pub distractions: Vec<Distraction>,
+    /// What pulled away from the focus period, noted with the next interruption.
+    pub distraction_note: String,
+    /// Micro reminders already sent in the current phase.
+    pub micro_reminders_sent: u32,
+    /// Since when the timer is stopped, waiting for the next phase to be started.
+    pub waiting_since: Option<Instant>,
+    /// Prompts sent since `waiting_since`.
+    pub idle_prompts_sent: u32,
+    /// Focus time left over by [`PomodoroTimer::bank_and_break`], added to the next focus period.
+    pub banked_sec: u32,
+    /// Seconds focused since the last long break, see [`LongBreakTrigger::FocusTime`].
+    focus_secs_since_long_break: u32,
+    phase_started_at: Option<DateTime<Local>>,
     counter_pipe: Sender<bool>,
+    /// Cleared once the countdown is dropped, which happens early only if its thread panicked.
+    countdown_alive: Arc<AtomicBool>,
+    /// Handed to a countdown started again by [`PomodoroTimer::restart_dead_countdown`].
+    to_subscription: UnboundedSender<TimerEvent>,
+    /// The countdown if it is ticked by hand, see `ManualTicks` in the tests.
+    manual_countdown: Option<Countdown>,
+    /// Events of the countdown thread, consumed by the timer's subscription.
+    pub events: Arc<Mutex<UnboundedReceiver<TimerEvent>>>,
+}
+
+/// Initial configuration of a [`PomodoroTimer`].
+pub(crate) struct PomodoroConfig {
+    /// The sequence of intervals, repeated once the last one is done.
+    /// A long break is modelled as an interval with a longer relax period.
+    pub pomodoro_lengths: Vec<PomodoroLength>,
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            pomodoro_lengths: vec![
+                PomodoroLength::new(25 * 60, 5 * 60),
+                PomodoroLength::new(25 * 60, 5 * 60),
+                PomodoroLength::new(25 * 60, 5 * 60),
+                PomodoroLength::new(25 * 60, 5 * 60),
+                PomodoroLength::new(25 * 60, 15 * 60),
+            ],
+        }
+    }
 }
 
 impl PomodoroTimer {
     pub fn new() -> Self {
-        let (to_pomodoro_timer, from_countdown) = mpsc::channel::<bool>();
-        //test
-        let pomodoro_lengths = vec![
-            PomodoroLength::new(10, 5),
-            PomodoroLength::new(7, 4)
-        ];
-        // let pomodoro_lengths = vec![
-        //     PomodoroLength::new(25 * 60, 5 * 60),
-        //     PomodoroLength::new(25 * 60, 5 * 60),
-        //     PomodoroLength::new(25 * 60, 5 * 60),
-        //     PomodoroLength::new(25 * 60, 5 * 60),
-        //     PomodoroLength::new(25 * 60, 15 * 60),
-        // ];
-        let remaining_sec = Arc::new(AtomicU32::new(pomodoro_lengths[0].focus));
-        let remaining_sec_clone = remaining_sec.clone();
+        Self::with_config(PomodoroConfig::default())
+    }
 
-        thread::spawn(move || {
-            let mut is_active = false;
-            loop {
-                is_active = match from_countdown.try_recv() {
-                    Ok(state) => { state }
-                    Err(_) => {
-                        is_active
-                    }
-                };
-                if is_active && remaining_sec_clone.as_ref().load(Ordering::SeqCst) > 0u32 {
-                    remaining_sec_clone.fetch_sub(1, Ordering::SeqCst);
-                }
-                sleep(Duration::from_secs(1));
-            }
-        });
+    pub fn with_config(config: PomodoroConfig) -> Self {
+        Self::with_tick_source(config, &RealTime)
+    }
 
-        Self {
+    /// Creates a timer counting down with the ticks of `tick_source`.
+    pub fn with_tick_source(config: PomodoroConfig, tick_source: &dyn TickSource) -> Self {
+        let (to_pomodoro_timer, from_timer) = mpsc::channel::<bool>();
+        let (to_subscription, events) = unbounded_channel::<TimerEvent>();
+        let pomodoro_lengths = if config.pomodoro_lengths.is_empty() {
+            PomodoroConfig::default().pomodoro_lengths
+        } else {
+            config.pomodoro_lengths
+        };
+        let initial_sec = pomodoro_lengths[0].focus;
+        let remaining_sec = Arc::new(AtomicU32::new(initial_sec));
+        let overtime_sec = Arc::new(AtomicU32::new(0));
+        let countdown = Countdown::new(from_timer, remaining_sec.clone(), overtime_sec.clone(), to_subscription.clone());
+        let countdown_alive = countdown.alive.clone();
+        let manual_countdown = tick_source.drive(countdown);
+
+        let mut timer = Self {
+            id: NEXT_TIMER_ID.fetch_add(1, Ordering::Relaxed),
             settings: Settings::new(),
             pomodoro_lengths,
             position: 0,
+            one_off_lengths: VecDeque::new(),
+            current_one_off: None,
             pomodoro_state: PomodoroState::Stop,
             pomodoro_phase: PomodoroPhase::BeforeFocus,
+            initial_sec,
             remaining_sec,
+            overtime_sec,
+            in_overtime: false,
+            break_after_completed_focus: false,
+            history: SessionHistory::default(),
+            note: String::new(),
+            interruptions: 0,
+            distractions: Vec::new(),
+            distraction_note: String::new(),
+            micro_reminders_sent: 0,
+            waiting_since: Some(Instant::now()),
+            idle_prompts_sent: 0,
+            banked_sec: 0,
+            focus_secs_since_long_break: 0,
+            phase_started_at: None,
             counter_pipe: to_pomodoro_timer,
+            countdown_alive,
+            to_subscription,
+            manual_countdown,
+            events: Arc::new(Mutex::new(events)),
+        };
+        timer.enter_start_phase();
+        timer
+    }
+
+    /// Counts down one second of a timer created with [`ManualTicks`], does nothing otherwise.
+    #[cfg(test)]
+    pub fn tick(&mut self) {
+        if let Some(countdown) = &mut self.manual_countdown {
+            countdown.tick();
         }
     }
 
     pub fn start(&mut self) {
-        self.counter_pipe.send(true).unwrap();
+        self.waiting_since = None;
+        self.phase_started_at = Some(Local::now());
+        self.send_to_countdown(true);
         self.pomodoro_state = PomodoroState::Run;
     }
 
     pub fn pause(&mut self) {
-        self.counter_pipe.send(false).unwrap();
+        self.send_to_countdown(false);
         self.pomodoro_state = PomodoroState::Pause;
     }
 
     pub fn resume(&mut self) {
-        self.counter_pipe.send(true).unwrap();
+        self.waiting_since = None;
+        self.send_to_countdown(true);
         self.pomodoro_state = PomodoroState::Run;
     }
 
     pub fn stop(&mut self) {
-        self.counter_pipe.send(false).unwrap();
+        self.send_to_countdown(false);
         self.pomodoro_state = PomodoroState::Stop;
+        self.waiting_since = Some(Instant::now());
+        self.idle_prompts_sent = 0;
+    }
+
+    /// Tells the countdown thread whether the timer runs.
+    /// The timer can't count down without it, which is logged rather than taking the app down.
+    fn send_to_countdown(&self, running: bool) {
+        if let Err(error) = self.counter_pipe.send(running) {
+            tracing::error!(timer = self.id, running, %error, "the countdown of the timer is gone");
+        }
     }
+
+    /// Starts the countdown thread again if it's gone, e.g. after a panic, so the timer doesn't freeze.
+    /// The new countdown goes on from the remaining time the old one left, as the timer runs or not.
+    /// Returns whether it had to be restarted.
+    pub fn restart_dead_countdown(&mut self) -> bool {
+        if self.manual_countdown.is_some() || self.countdown_alive.load(Ordering::SeqCst) {
+            return false;
+        }
+        tracing::error!(timer = self.id, "the countdown of the timer stopped, restarting it");
+        let (counter_pipe, from_timer) = mpsc::channel::<bool>();
+        let countdown = Countdown::new(from_timer, self.remaining_sec.clone(), self.overtime_sec.clone(), self.to_subscription.clone());
+        self.countdown_alive = countdown.alive.clone();
+        RealTime.drive(countdown);
+        self.counter_pipe = counter_pipe;
+        self.send_to_countdown(self.pomodoro_state == PomodoroState::Run);
+        true
+    }
+
+    /// Goes back to the start of the first interval, banked focus time and one-off intervals are dropped.
     pub fn reset(&mut self) {
+        self.record_phase(false);
+        self.stop();
+        self.banked_sec = 0;
+        self.one_off_lengths.clear();
+        self.current_one_off = None;
+        self.focus_secs_since_long_break = 0;
+        // a cycle starting with a break has no pomodoro before it
+        self.break_after_completed_focus = false;
+        self.enter_start_phase();
+    }
+
+    /// Loads the phase the cycle starts with, see [`StartPhase`].
+    /// Starting with a break loads the break of the last interval, so the first interval follows it.
+    fn enter_start_phase(&mut self) {
+        if self.settings.start_phase == StartPhase::Relax && !self.settings.continuous_focus {
+            self.position = self.pomodoro_lengths.len() - 1;
+            self.pomodoro_phase = PomodoroPhase::BeforeRelax;
+            self.set_phase_length(self.pomodoro_lengths[self.position].relax);
+        } else {
+            self.position = 0;
+            self.pomodoro_phase = PomodoroPhase::BeforeFocus;
+            self.set_phase_length(self.pomodoro_lengths[0].focus);
+        }
+    }
+
+    /// Number of focus periods completed in the current cycle.
+    /// A one-off interval isn't part of the cycle, it follows the completed interval at `position`.
+    pub fn completed_in_cycle(&self) -> usize {
+        if self.current_one_off.is_some() {
+            return self.position + 1;
+        }
+        match self.pomodoro_phase {
+            PomodoroPhase::BeforeFocus | PomodoroPhase::Focus => self.position,
+            PomodoroPhase::BeforeRelax | PomodoroPhase::Relax => self.position + 1,
+        }
+    }
+
+    /// Ends the focus period and loads the upcoming break.
+    pub fn enter_before_relax(&mut self, completed: bool) {
+        self.record_phase(completed);
+        self.break_after_completed_focus = completed;
+        self.pomodoro_phase = PomodoroPhase::BeforeRelax;
+        self.stop();
+        let relax = if self.settings.is_lunch_time(Local::now().time()) {
+            self.settings.lunch_break_length
+        } else if self.settings.long_break_trigger == LongBreakTrigger::FocusTime {
+            self.focus_time_relax()
+        } else {
+            self.current_length().relax
+        };
+        self.set_phase_length(relax);
+    }
+
+    /// The longest break of the intervals once enough time was spent focusing, the shortest one otherwise.
+    fn focus_time_relax(&mut self) -> u32 {
+        let relax_lengths = self.pomodoro_lengths.iter().map(|length| length.relax);
+        let (Some(shortest), Some(longest)) = (relax_lengths.clone().min(), relax_lengths.max()) else {
+            return 0;
+        };
+        if self.focus_secs_since_long_break >= self.settings.long_break_focus_mins.saturating_mul(60) {
+            self.focus_secs_since_long_break = 0;
+            longest
+        } else {
+            shortest
+        }
+    }
+
+    /// Ends the break and loads the focus period of the next interval, with the banked focus time added.
+    /// A waiting one-off interval comes before the next interval of the sequence.
+    pub fn enter_before_focus(&mut self, completed: bool) {
+        self.record_phase(completed);
+        self.break_after_completed_focus = false;
+        self.current_one_off = self.one_off_lengths.pop_front();
+        // the sequence goes on where it was left for the one-off intervals
+        if self.current_one_off.is_none() {
+            self.position += 1;
+            if self.position >= self.pomodoro_lengths.len() {
+                self.position = 0;
+            }
+        }
+        self.pomodoro_phase = PomodoroPhase::BeforeFocus;
+        self.stop();
+        let focus = self.current_length().focus.saturating_add(std::mem::take(&mut self.banked_sec));
+        self.set_phase_length(focus);
+    }
+
+    /// Ends the focus period and starts the focus period of the next interval right away.
+    pub fn enter_next_focus(&mut self, completed: bool) {
+        self.enter_before_focus(completed);
+        self.pomodoro_phase = PomodoroPhase::Focus;
+        self.start();
+    }
+
+    /// Starts a focus period of `secs` in place of the current phase.
+    /// The intervals stay untouched, so the sequence continues with the current interval's break.
+    pub fn quick_start(&mut self, secs: u32) {
+        self.record_phase(false);
+        self.stop();
+        self.pomodoro_phase = PomodoroPhase::Focus;
+        self.set_phase_length(secs);
+        self.start();
+    }
+
+    /// Keeps the ended focus period running, counting the time worked past its end until it's moved on by hand.
+    pub fn enter_overtime(&mut self) {
+        self.in_overtime = true;
+        self.overtime_sec.store(0, Ordering::SeqCst);
+    }
+
+    pub fn is_in_overtime(&self) -> bool {
+        self.in_overtime
+    }
+
+    /// Whether the running phase has no time left and has to end, e.g. after it was resumed at zero,
+    /// which the countdown doesn't report as it only reports reaching zero.
+    /// Overtime goes on until it's moved on by hand.
+    pub fn has_run_out(&self) -> bool {
+        self.pomodoro_state == PomodoroState::Run
+            && self.remaining_sec.load(Ordering::SeqCst) == 0
+            && !self.in_overtime
+    }
+
+    /// Whether a running focus period can only run out, see [`Settings::focus_lock`].
+    /// Overtime is past the end, so it's not locked.
+    pub fn is_locked(&self) -> bool {
+        self.settings.focus_lock
+            && self.pomodoro_phase == PomodoroPhase::Focus
+            && self.pomodoro_state == PomodoroState::Run
+            && !self.in_overtime
+    }
+
+    /// Whether the break can't be skipped, see [`Settings::purist`].
+    pub fn keeps_break(&self) -> bool {
+        self.settings.purist && matches!(self.pomodoro_phase, PomodoroPhase::BeforeRelax | PomodoroPhase::Relax)
+    }
+
+    /// Turns the purist mode on or off, see [`Settings::apply_purist`].
+    /// Turning it on starts over with the intervals of the technique, banked time and one-off intervals are dropped.
+    pub fn set_purist(&mut self, purist: bool) {
+        self.settings.purist = purist;
+        if purist {
+            self.settings.apply_purist();
+            self.banked_sec = 0;
+            self.one_off_lengths.clear();
+            self.current_one_off = None;
+            self.set_lengths(PURIST_PRESET.lengths());
+        }
+    }
+
+    /// Skips the current or upcoming phase.
+    /// Depending on the settings, the skipped time is discarded or added to the next phase.
+    /// A focus period in overtime was completed, so it moves on as completed.
+    pub fn skip(&mut self) {
+        let remaining_sec = self.remaining_sec.load(Ordering::SeqCst);
+        let completed = self.in_overtime;
+        match self.pomodoro_phase {
+            // without breaks, the next phase is the focus period of the next interval
+            PomodoroPhase::BeforeFocus | PomodoroPhase::Focus if self.settings.continuous_focus => self.enter_before_focus(completed),
+            PomodoroPhase::BeforeFocus | PomodoroPhase::Focus => self.enter_before_relax(completed),
+            PomodoroPhase::BeforeRelax | PomodoroPhase::Relax => self.enter_before_focus(completed),
+        }
+        // carried over time after time can't grow a phase past the longest length
+        if self.settings.skip_behavior == SkipBehavior::CarryOver {
+            let secs = self.initial_sec.saturating_add(remaining_sec);
+            self.set_phase_length(secs.clamp(*LENGTH_RANGE.start(), *LENGTH_RANGE.end()));
+        }
+    }
+
+    /// Length of the focus period after the current break, with the banked focus time.
+    pub fn next_focus_secs(&self) -> u32 {
+        let next = self.one_off_lengths.front()
+            .unwrap_or_else(|| &self.pomodoro_lengths[(self.position + 1) % self.pomodoro_lengths.len()]);
+        next.focus.saturating_add(self.banked_sec)
+    }
+
+    /// The interval being played, a one-off interval or the one at `position`.
+    pub fn current_length(&self) -> &PomodoroLength {
+        self.current_one_off.as_ref().unwrap_or(&self.pomodoro_lengths[self.position])
+    }
+
+    /// Adds one more pomodoro just this once, a copy of the first interval played after the current interval.
+    /// It's consumed once it's played, so the next cycle is the saved sequence again.
+    pub fn add_one_off(&mut self) {
+        let mut length = self.pomodoro_lengths[0].clone();
+        // a long break would make the long break come twice
+        length.relax = self.pomodoro_lengths.iter().map(|length| length.relax).min().unwrap_or(length.relax);
+        self.one_off_lengths.push_back(length);
+    }
+
+    /// One-off intervals added and not played yet.
+    pub fn one_offs_waiting(&self) -> usize {
+        self.one_off_lengths.len()
+    }
+
+    /// Ends the focus period early, e.g. for a meeting, and starts its break right away.
+    /// The remaining focus time is banked, up to [`MAX_BANKED_SEC`], and added to the next focus period.
+    /// Returns whether there was a focus period to bank, there is none without breaks.
+    pub fn bank_and_break(&mut self) -> bool {
+        if self.pomodoro_phase != PomodoroPhase::Focus || self.settings.continuous_focus {
+            return false;
+        }
+        let remaining_sec = self.remaining_sec.load(Ordering::SeqCst);
+        self.banked_sec = self.banked_sec.saturating_add(remaining_sec).min(MAX_BANKED_SEC);
+        // nothing is left to bank in overtime, the focus period was completed
+        self.enter_before_relax(self.in_overtime);
+        self.pomodoro_phase = PomodoroPhase::Relax;
+        self.start();
+        true
+    }
+
+    /// Takes a break right away, whatever the phase.
+    /// A focus period, running, paused or waiting, ends as not completed, or as completed in overtime,
+    /// and the break it would have been followed by starts. A waiting or paused break starts.
+    /// Returns the phase that was left, `None` if the break runs already.
+    pub fn break_now(&mut self) -> Option<PomodoroPhase> {
+        let left = self.pomodoro_phase;
+        match (left, self.pomodoro_state) {
+            (PomodoroPhase::Relax, PomodoroState::Run) => return None,
+            (PomodoroPhase::Relax, PomodoroState::Pause) => self.resume(),
+            (PomodoroPhase::Relax | PomodoroPhase::BeforeRelax, _) => {
+                self.pomodoro_phase = PomodoroPhase::Relax;
+                self.start();
+            }
+            (PomodoroPhase::BeforeFocus | PomodoroPhase::Focus, _) => {
+                self.enter_before_relax(self.in_overtime);
+                self.pomodoro_phase = PomodoroPhase::Relax;
+                self.start();
+            }
+        }
+        Some(left)
+    }
+
+    /// Adds the current focus or relax phase to the history.
+    /// The note of a focus period is kept with it if it was completed.
+    pub fn record_phase(&mut self, completed: bool) {
+        let overtime_secs = if std::mem::take(&mut self.in_overtime) {
+            self.overtime_sec.load(Ordering::SeqCst)
+        } else {
+            0
+        };
+        let (note, interruptions, distractions) = if self.pomodoro_phase == PomodoroPhase::Focus {
+            (std::mem::take(&mut self.note), std::mem::take(&mut self.interruptions), std::mem::take(&mut self.distractions))
+        } else {
+            (String::new(), 0, Vec::new())
+        };
+        let Some(start) = self.phase_started_at.take() else {
+            return;
+        };
+        if self.pomodoro_phase != PomodoroPhase::Focus && self.pomodoro_phase != PomodoroPhase::Relax {
+            return;
+        }
+        let remaining_sec = self.remaining_sec.load(Ordering::SeqCst);
+        let actual_secs = self.initial_sec.saturating_sub(remaining_sec);
+        if self.pomodoro_phase == PomodoroPhase::Focus {
+            self.focus_secs_since_long_break = self.focus_secs_since_long_break.saturating_add(actual_secs);
+        }
+        self.history.push(SessionRecord {
+            start,
+            phase: self.pomodoro_phase,
+            planned_secs: self.initial_sec,
+            actual_secs,
+            completed,
+            note: if completed { note } else { String::new() },
+            interruptions,
+            overtime_secs,
+            distractions,
+        });
+    }
+
+    /// Counts an interruption of a running focus period, which keeps running.
+    /// It's logged with the time and the `distraction_note`, which is cleared for the next one.
+    /// In the purist mode the focus period is void instead, it's recorded as not completed and starts over.
+    /// Returns whether it was counted.
+    pub fn interrupt(&mut self) -> bool {
+        if self.pomodoro_phase != PomodoroPhase::Focus || self.pomodoro_state != PomodoroState::Run {
+            return false;
+        }
+        self.interruptions += 1;
+        let note = std::mem::take(&mut self.distraction_note).trim().to_string();
+        self.distractions.push(Distraction { at: Local::now(), note });
+        if self.settings.purist {
+            self.record_phase(false);
+            self.stop();
+            self.pomodoro_phase = PomodoroPhase::BeforeFocus;
+            self.set_phase_length(self.current_length().focus);
+        }
+        true
+    }
+
+    /// Lengthens or shortens the phase waiting to be started by `delta_secs`, within `LENGTH_RANGE`.
+    /// The interval keeps its length unless `keep`, then the next cycles have the new length as well.
+    /// Returns whether there was a phase waiting, a running one isn't changed.
+    pub fn adjust_upcoming_length(&mut self, delta_secs: i32, keep: bool) -> bool {
+        if self.pomodoro_state != PomodoroState::Stop {
+            return false;
+        }
+        let adjust = |secs: u32| (secs as i64 + delta_secs as i64)
+            .clamp(*LENGTH_RANGE.start() as i64, *LENGTH_RANGE.end() as i64) as u32;
+        // a one-off interval isn't kept anyway
+        let keep = keep && self.current_one_off.is_none();
+        let length = &mut self.pomodoro_lengths[self.position];
+        match self.pomodoro_phase {
+            PomodoroPhase::BeforeFocus if keep => length.focus = adjust(length.focus),
+            PomodoroPhase::BeforeRelax if keep => length.relax = adjust(length.relax),
+            PomodoroPhase::BeforeFocus | PomodoroPhase::BeforeRelax => {}
+            PomodoroPhase::Focus | PomodoroPhase::Relax => return false,
+        }
+        self.set_phase_length(adjust(self.initial_sec));
+        true
+    }
+
+    /// Loads the length of the upcoming phase into the countdown.
+    pub fn set_phase_length(&mut self, secs: u32) {
+        self.micro_reminders_sent = 0;
+        self.overtime_sec.store(0, Ordering::SeqCst);
+        self.initial_sec = secs;
+        self.remaining_sec.store(secs, Ordering::SeqCst);
+    }
+
+    /// Time until the next long break starts, counting the rest of the current phase and the intervals before it.
+    /// The intervals with the longest break have a long break, so there are none if all breaks are equally long,
+    /// or without breaks at all.
+    /// It's unknown when the long break follows from the focus time, as that depends on the time focused until then.
+    pub fn time_until_long_break(&self) -> Option<Duration> {
+        if self.settings.continuous_focus || self.settings.long_break_trigger == LongBreakTrigger::FocusTime {
+            return None;
+        }
+        let longest = self.pomodoro_lengths.iter().map(|length| length.relax).max()?;
+        let shortest = self.pomodoro_lengths.iter().map(|length| length.relax).min()?;
+        if longest == shortest {
+            return None;
+        }
+        let is_long = |length: &PomodoroLength| length.relax == longest;
+        let current = self.current_length();
+        let mut secs = self.remaining_sec.load(Ordering::SeqCst) as u64;
+        match self.pomodoro_phase {
+            PomodoroPhase::BeforeFocus | PomodoroPhase::Focus => {
+                if is_long(current) {
+                    return Some(Duration::from_secs(secs));
+                }
+                secs += current.relax as u64;
+            }
+            PomodoroPhase::BeforeRelax if is_long(current) => return Some(Duration::ZERO),
+            // the current break, even a long one, is followed by the next interval
+            PomodoroPhase::BeforeRelax | PomodoroPhase::Relax => {}
+        }
+        let len = self.pomodoro_lengths.len();
+        let sequence = (1..=len).map(|step| &self.pomodoro_lengths[(self.position + step) % len]);
+        for length in self.one_off_lengths.iter().chain(sequence) {
+            secs += length.focus as u64;
+            if is_long(length) {
+                return Some(Duration::from_secs(secs));
+            }
+            secs += length.relax as u64;
+        }
+        None
+    }
+
+    /// Time until the current cycle ends with the break of the last interval,
+    /// counting the rest of the current phase as if it ran from now on.
+    pub fn time_until_cycle_end(&self) -> Duration {
+        let continuous_focus = self.settings.continuous_focus;
+        let current = self.current_length();
+        let mut secs = self.remaining_sec.load(Ordering::SeqCst) as u64;
+        if matches!(self.pomodoro_phase, PomodoroPhase::BeforeFocus | PomodoroPhase::Focus) && !continuous_focus {
+            secs += current.relax as u64;
+        }
+        for length in self.one_off_lengths.iter().chain(&self.pomodoro_lengths[self.position + 1..]) {
+            secs += length.focus as u64;
+            if !continuous_focus {
+                secs += length.relax as u64;
+            }
+        }
+        Duration::from_secs(secs)
+    }
+
+    /// The sound to play at the end of the current phase, the interval's own if it has one.
+    /// It's read from the settings as they are when called, nothing is kept from the start of the phase.
+    pub fn phase_end_sound(&self) -> &str {
+        let length = self.current_length();
+        match self.pomodoro_phase {
+            PomodoroPhase::BeforeFocus | PomodoroPhase::Focus => self.settings.get_end_of_focus_sound_for(length.focus_end_sound),
+            PomodoroPhase::BeforeRelax | PomodoroPhase::Relax => self.settings.get_end_of_relax_sound_for(length.relax_end_sound),
+        }
+    }
+
+    pub fn snapshot(&self, now: DateTime<Local>) -> TimerSnapshot {
+        TimerSnapshot {
+            position: self.position,
+            phase: self.pomodoro_phase,
+            state: self.pomodoro_state,
+            initial_sec: self.initial_sec,
+            remaining_sec: self.remaining_sec.load(Ordering::SeqCst),
+            banked_sec: self.banked_sec,
+            pomodoro_lengths: self.pomodoro_lengths.clone(),
+            settings: self.settings.saved(),
+            saved_at: now,
+        }
+    }
+
+    /// Continues where `snapshot` left off.
+    /// A paused phase or one that hasn't started yet is restored as it was.
+    /// A running phase either continues with the time passed since `saved_at` taken off,
+    /// or is restored paused if `restore_paused` is set.
+    /// A phase that would have ended in the meantime is restored running at zero, so it ends with the next event.
+    pub fn restore(&mut self, snapshot: &TimerSnapshot, restore_paused: bool, now: DateTime<Local>) {
+        self.restore_lengths(snapshot);
+        self.position = snapshot.position.min(self.pomodoro_lengths.len() - 1);
+        self.pomodoro_phase = snapshot.phase;
+        self.initial_sec = snapshot.initial_sec;
+        self.banked_sec = snapshot.banked_sec.min(MAX_BANKED_SEC);
+        let mut remaining_sec = snapshot.remaining_sec;
+        match (snapshot.state, snapshot.phase) {
+            (_, PomodoroPhase::BeforeFocus | PomodoroPhase::BeforeRelax) | (PomodoroState::Stop, _) => {
+                self.stop();
+            }
+            (PomodoroState::Pause, _) => {
+                self.phase_started_at = Some(snapshot.saved_at);
+                self.pause();
+            }
+            (PomodoroState::Run, _) if restore_paused => {
+                self.phase_started_at = Some(snapshot.saved_at);
+                self.pause();
+            }
+            (PomodoroState::Run, _) => {
+                let elapsed_sec = (now - snapshot.saved_at).num_seconds().clamp(0, u32::MAX as i64) as u32;
+                remaining_sec = remaining_sec.saturating_sub(elapsed_sec);
+                self.phase_started_at = Some(snapshot.saved_at);
+                self.resume();
+            }
+        }
+        self.remaining_sec.store(remaining_sec, Ordering::SeqCst);
+    }
+
+    /// Continues at the interval `snapshot` was at, with its focus period waiting to be started.
+    /// Nothing of a half-finished phase is restored, a focus period left during its break counts as done.
+    pub fn restore_position(&mut self, snapshot: &TimerSnapshot) {
+        self.restore_lengths(snapshot);
+        let mut position = snapshot.position;
+        if matches!(snapshot.phase, PomodoroPhase::BeforeRelax | PomodoroPhase::Relax) {
+            position += 1;
+        }
+        self.position = position % self.pomodoro_lengths.len();
+        self.pomodoro_phase = PomodoroPhase::BeforeFocus;
         self.stop();
-        self.remaining_sec.store(0, Ordering::SeqCst);
+        self.set_phase_length(self.pomodoro_lengths[self.position].focus);
+    }
+
+    /// Takes over the settings the timer was saved with, before its state is restored.
+    /// The rules of the purist mode are locked again, with its intervals.
+    pub fn restore_settings(&mut self, saved: &SavedSettings) {
+        self.settings.restore(saved);
+        self.set_purist(saved.purist);
+    }
+
+    /// Takes over the intervals of `snapshot`, a config written before they were saved has none.
+    /// The purist mode fixes them, so they're kept then.
+    fn restore_lengths(&mut self, snapshot: &TimerSnapshot) {
+        if !snapshot.pomodoro_lengths.is_empty() && !self.settings.purist {
+            self.pomodoro_lengths = snapshot.pomodoro_lengths.clone();
+        }
+    }
+
+    /// Replaces all intervals, e.g. with the ones of a preset.
+    /// Starts over with the first interval, a stopped timer shows the length of its upcoming phase right away.
+    pub fn set_lengths(&mut self, lengths: Vec<PomodoroLength>) {
+        if lengths.is_empty() {
+            return;
+        }
+        self.pomodoro_lengths = lengths;
         self.position = 0;
+        if self.pomodoro_state == PomodoroState::Stop {
+            let length = &self.pomodoro_lengths[0];
+            let secs = match self.pomodoro_phase {
+                PomodoroPhase::BeforeRelax | PomodoroPhase::Relax => length.relax,
+                PomodoroPhase::BeforeFocus | PomodoroPhase::Focus => length.focus,
+            };
+            self.set_phase_length(secs);
+        }
+    }
+
+    pub fn remove_interval(&mut self, index: usize) -> Option<PomodoroLength> {
+        if self.pomodoro_lengths.len() <= 1 || index >= self.pomodoro_lengths.len() {
+            return None;
+        }
+        let removed = self.pomodoro_lengths.remove(index);
+        if index < self.position {
+            self.position -= 1;
+        } else if self.position >= self.pomodoro_lengths.len() {
+            self.position = 0;
+        }
+        Some(removed)
     }
+
+    pub fn insert_interval(&mut self, index: usize, length: PomodoroLength) {
+        let index = index.min(self.pomodoro_lengths.len());
+        self.pomodoro_lengths.insert(index, length);
+        if index < self.position {
+            self.position += 1;
+        }
+    }
+}
+/// Counts the remaining seconds of a [`PomodoroTimer`] down while it runs.
+pub(crate) struct Countdown {
+    /// Whether the timer runs, sent on every change.
+    from_timer: Receiver<bool>,
+    is_active: bool,
+    remaining_sec: Arc<AtomicU32>,
+    overtime_sec: Arc<AtomicU32>,
+    to_subscription: UnboundedSender<TimerEvent>,
+    /// Shared with the timer, see [`PomodoroTimer::restart_dead_countdown`].
+    alive: Arc<AtomicBool>,
 }
+
+impl Countdown {
+    fn new(from_timer: Receiver<bool>, remaining_sec: Arc<AtomicU32>, overtime_sec: Arc<AtomicU32>,
+           to_subscription: UnboundedSender<TimerEvent>) -> Self {
+        Self {
+            from_timer,
+            is_active: false,
+            remaining_sec,
+            overtime_sec,
+            to_subscription,
+            alive: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Takes off one second if the timer runs, `false` once the timer was dropped.
+    /// Past zero it counts the overtime up, which only lasts while the timer is kept in overtime,
+    /// otherwise it moves on to the next phase at zero.
+    fn tick(&mut self) -> bool {
+        loop {
+            match self.from_timer.try_recv() {
+                Ok(state) => self.is_active = state,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return false,
+            }
+        }
+        if self.is_active && self.remaining_sec.load(Ordering::SeqCst) > 0u32 {
+            let remaining_sec = self.remaining_sec.fetch_sub(1, Ordering::SeqCst) - 1;
+            // reported from here, so the transition doesn't wait for the next UI refresh
+            if remaining_sec == 0 && self.to_subscription.send(TimerEvent::Elapsed).is_err() {
+                tracing::warn!("the end of a phase was reported after the timer's subscription ended");
+            }
+        } else if self.is_active {
+            self.overtime_sec.fetch_add(1, Ordering::SeqCst);
+        }
+        true
+    }
+}
+
+impl Drop for Countdown {
+    /// Also runs while a panic of the countdown thread unwinds.
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Where the ticks of a [`Countdown`] come from.
+pub(crate) trait TickSource {
+    /// Takes over ticking `countdown`, or hands it back to be ticked with `PomodoroTimer::tick`.
+    fn drive(&self, countdown: Countdown) -> Option<Countdown>;
+}
+
+/// Ticks once a second on its own thread.
+pub(crate) struct RealTime;
+
+impl TickSource for RealTime {
+    fn drive(&self, mut countdown: Countdown) -> Option<Countdown> {
+        thread::spawn(move || {
+            while countdown.tick() {
+                sleep(Duration::from_secs(1));
+            }
+        });
+        None
+    }
+}
+
+/// Leaves the ticks to the caller, so exact sequences of seconds can be replayed without waiting.
+#[cfg(test)]
+pub(crate) struct ManualTicks;
+
+#[cfg(test)]
+impl TickSource for ManualTicks {
+    fn drive(&self, countdown: Countdown) -> Option<Countdown> {
+        Some(countdown)
+    }
+}
+
+/// The lengths are written like `25m` or `1h30m` in the config, see `duration_string`.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub(crate) struct PomodoroLength {
+    #[serde(with = "crate::core::duration_string")]
     pub focus: u32,
+    #[serde(with = "crate::core::duration_string")]
     pub relax: u32,
+    /// Replaces the sound at the end of the focus period, `None` for the one of the settings.
+    #[serde(default)]
+    pub focus_end_sound: Option<usize>,
+    #[serde(default)]
+    pub relax_end_sound: Option<usize>,
 }
 
 impl PomodoroLength {
-    fn new(focus: u32, relax: u32) -> Self {
+    pub fn new(focus: u32, relax: u32) -> Self {
         Self {
             focus,
             relax,
+            focus_end_sound: None,
+            relax_end_sound: None,
         }
     }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum TimerEvent {
+    /// The countdown of a running phase reached zero.
+    Elapsed,
+}
+
+/// The state of a [`PomodoroTimer`] as persisted when the app closes.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub(crate) struct TimerSnapshot {
+    pub position: usize,
+    pub phase: PomodoroPhase,
+    pub state: PomodoroState,
+    pub initial_sec: u32,
+    pub remaining_sec: u32,
+    #[serde(default)]
+    pub banked_sec: u32,
+    /// The intervals, empty in configs from before they were saved.
+    #[serde(default)]
+    pub pomodoro_lengths: Vec<PomodoroLength>,
+    #[serde(default)]
+    pub settings: SavedSettings,
+    /// To account for the time that passed while the app was closed.
+    pub saved_at: DateTime<Local>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub(crate) enum PomodoroState {
     Stop,
     Run,
     Pause,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub(crate) enum PomodoroPhase {
     BeforeFocus,
     Focus,
     BeforeRelax,
     Relax,
-}
\ No newline at end of file
+}
+
+impl PomodoroPhase {
+    /// The text shown for the phase, in headings, notifications and the window title.
+    pub fn localized_name(&self) -> String {
+        match self {
+            PomodoroPhase::BeforeFocus => fl!("before-focus"),
+            PomodoroPhase::Focus => fl!("focus-running"),
+            PomodoroPhase::BeforeRelax => fl!("before-relax"),
+            PomodoroPhase::Relax => fl!("relax-running"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::settings::SettingMessage;
+
+    /// Three intervals of a few seconds, the last one with the long break.
+    fn manual_timer() -> PomodoroTimer {
+        let config = PomodoroConfig {
+            pomodoro_lengths: vec![PomodoroLength::new(3, 1), PomodoroLength::new(3, 1), PomodoroLength::new(3, 2)],
+        };
+        PomodoroTimer::with_tick_source(config, &ManualTicks)
+    }
+
+    fn remaining(timer: &PomodoroTimer) -> u32 {
+        timer.remaining_sec.load(Ordering::SeqCst)
+    }
+
+    fn elapsed(timer: &PomodoroTimer) -> bool {
+        matches!(timer.events.try_lock().unwrap().try_recv(), Ok(TimerEvent::Elapsed))
+    }
+
+    /// Starts the waiting phase and ticks until it runs out, then moves on to the next phase like the app does.
+    fn run_phase(timer: &mut PomodoroTimer) {
+        timer.pomodoro_phase = match timer.pomodoro_phase {
+            PomodoroPhase::BeforeFocus => PomodoroPhase::Focus,
+            PomodoroPhase::BeforeRelax => PomodoroPhase::Relax,
+            phase => phase,
+        };
+        timer.start();
+        for _ in 0..timer.initial_sec {
+            assert!(!elapsed(timer));
+            timer.tick();
+        }
+        assert!(timer.has_run_out());
+        assert!(elapsed(timer));
+        match timer.pomodoro_phase {
+            PomodoroPhase::Focus => timer.enter_before_relax(true),
+            _ => timer.enter_before_focus(true),
+        }
+    }
+
+    #[test]
+    fn ticks_through_a_cycle() {
+        let mut timer = manual_timer();
+        assert_eq!(timer.pomodoro_phase, PomodoroPhase::BeforeFocus);
+        assert_eq!(timer.completed_in_cycle(), 0);
+        for position in 0..3 {
+            run_phase(&mut timer);
+            assert_eq!(timer.pomodoro_phase, PomodoroPhase::BeforeRelax);
+            assert_eq!(timer.position, position);
+            assert_eq!(timer.completed_in_cycle(), position + 1);
+            // the last interval has the long break
+            assert_eq!(timer.initial_sec, if position == 2 { 2 } else { 1 });
+            run_phase(&mut timer);
+            assert_eq!(timer.pomodoro_phase, PomodoroPhase::BeforeFocus);
+            assert_eq!(timer.initial_sec, 3);
+        }
+        assert_eq!(timer.position, 0);
+        assert_eq!(timer.completed_in_cycle(), 0);
+        let completed_focus = timer.history.records.iter()
+            .filter(|record| record.phase == PomodoroPhase::Focus && record.completed)
+            .count();
+        assert_eq!(completed_focus, 3);
+    }
+
+    #[test]
+    fn paused_timer_doesnt_count_down() {
+        let mut timer = manual_timer();
+        timer.pomodoro_phase = PomodoroPhase::Focus;
+        timer.start();
+        timer.tick();
+        timer.pause();
+        timer.tick();
+        timer.tick();
+        assert_eq!(remaining(&timer), 2);
+        timer.resume();
+        timer.tick();
+        assert_eq!(remaining(&timer), 1);
+    }
+
+    #[test]
+    fn resumed_at_zero_has_run_out() {
+        let mut timer = manual_timer();
+        let now = Local::now();
+        let snapshot = TimerSnapshot {
+            phase: PomodoroPhase::Focus,
+            state: PomodoroState::Pause,
+            remaining_sec: 0,
+            ..timer.snapshot(now)
+        };
+        timer.restore(&snapshot, false, now);
+        assert!(!timer.has_run_out());
+        timer.resume();
+        timer.tick();
+        assert!(!elapsed(&timer));
+        assert!(timer.has_run_out());
+        timer.enter_overtime();
+        assert!(!timer.has_run_out());
+    }
+
+    #[test]
+    fn skipped_time_is_carried_over() {
+        let mut timer = manual_timer();
+        timer.settings.skip_behavior = SkipBehavior::CarryOver;
+        timer.pomodoro_phase = PomodoroPhase::Focus;
+        timer.start();
+        timer.tick();
+        timer.skip();
+        assert_eq!(timer.pomodoro_phase, PomodoroPhase::BeforeRelax);
+        assert_eq!(timer.initial_sec, 1 + 2);
+        assert_eq!(remaining(&timer), 1 + 2);
+    }
+
+    #[test]
+    fn skipped_time_is_discarded() {
+        let mut timer = manual_timer();
+        timer.pomodoro_phase = PomodoroPhase::Focus;
+        timer.start();
+        timer.tick();
+        timer.skip();
+        assert_eq!(timer.initial_sec, 1);
+    }
+
+    #[test]
+    fn carried_over_time_stays_within_the_lengths() {
+        let longest = *LENGTH_RANGE.end();
+        let mut timer = manual_timer();
+        timer.settings.skip_behavior = SkipBehavior::CarryOver;
+        timer.set_lengths(vec![PomodoroLength::new(longest, longest)]);
+        timer.skip();
+        assert_eq!(timer.initial_sec, longest);
+        timer.skip();
+        assert_eq!(timer.initial_sec, longest);
+    }
+
+    #[test]
+    fn banked_time_is_added_to_the_next_focus_period() {
+        let mut timer = manual_timer();
+        timer.pomodoro_phase = PomodoroPhase::Focus;
+        timer.start();
+        timer.tick();
+        assert!(timer.bank_and_break());
+        assert_eq!(timer.pomodoro_phase, PomodoroPhase::Relax);
+        assert_eq!(timer.banked_sec, 2);
+        timer.enter_before_focus(true);
+        assert_eq!(timer.initial_sec, 3 + 2);
+        assert_eq!(timer.banked_sec, 0);
+    }
+
+    #[test]
+    fn banked_time_is_capped() {
+        let mut timer = manual_timer();
+        timer.set_lengths(vec![PomodoroLength::new(MAX_BANKED_SEC + 60, 1)]);
+        timer.pomodoro_phase = PomodoroPhase::Focus;
+        timer.start();
+        assert!(timer.bank_and_break());
+        assert_eq!(timer.banked_sec, MAX_BANKED_SEC);
+    }
+
+    #[test]
+    fn time_until_long_break_counts_the_intervals_before_it() {
+        let mut timer = manual_timer();
+        // focus, break, focus, break, then the focus period before the long break
+        assert_eq!(timer.time_until_long_break(), Some(Duration::from_secs(3 + 1 + 3 + 1 + 3)));
+        for _ in 0..5 {
+            run_phase(&mut timer);
+        }
+        assert_eq!((timer.position, timer.pomodoro_phase), (2, PomodoroPhase::BeforeRelax));
+        assert_eq!(timer.time_until_long_break(), Some(Duration::ZERO));
+        // the next one is at the end of the next cycle
+        timer.pomodoro_phase = PomodoroPhase::Relax;
+        timer.start();
+        timer.tick();
+        assert_eq!(timer.time_until_long_break(), Some(Duration::from_secs(1 + 3 + 1 + 3 + 1 + 3)));
+    }
+
+    #[test]
+    fn no_long_break_without_a_longer_break() {
+        let mut timer = manual_timer();
+        timer.set_lengths(vec![PomodoroLength::new(3, 1), PomodoroLength::new(3, 1)]);
+        assert_eq!(timer.time_until_long_break(), None);
+        let mut timer = manual_timer();
+        timer.settings.continuous_focus = true;
+        assert_eq!(timer.time_until_long_break(), None);
+        let mut timer = manual_timer();
+        timer.settings.long_break_trigger = LongBreakTrigger::FocusTime;
+        assert_eq!(timer.time_until_long_break(), None);
+    }
+
+    /// A snapshot of a focus period with 100 seconds left, taken 10 seconds before `now`.
+    fn focus_snapshot(timer: &PomodoroTimer, state: PomodoroState, now: DateTime<Local>) -> TimerSnapshot {
+        TimerSnapshot {
+            phase: PomodoroPhase::Focus,
+            state,
+            initial_sec: 200,
+            remaining_sec: 100,
+            saved_at: now - chrono::Duration::seconds(10),
+            ..timer.snapshot(now)
+        }
+    }
+
+    #[test]
+    fn restores_a_paused_phase_as_it_was() {
+        let now = Local::now();
+        let mut timer = manual_timer();
+        timer.restore(&focus_snapshot(&timer, PomodoroState::Pause, now), false, now);
+        assert_eq!((timer.pomodoro_phase, timer.pomodoro_state), (PomodoroPhase::Focus, PomodoroState::Pause));
+        assert_eq!((timer.initial_sec, remaining(&timer)), (200, 100));
+    }
+
+    #[test]
+    fn restores_a_running_phase_with_the_time_passed() {
+        let now = Local::now();
+        let mut timer = manual_timer();
+        timer.restore(&focus_snapshot(&timer, PomodoroState::Run, now), false, now);
+        assert_eq!(timer.pomodoro_state, PomodoroState::Run);
+        assert_eq!(remaining(&timer), 90);
+        timer.tick();
+        assert_eq!(remaining(&timer), 89);
+    }
+
+    #[test]
+    fn restores_a_running_phase_paused() {
+        let now = Local::now();
+        let mut timer = manual_timer();
+        timer.restore(&focus_snapshot(&timer, PomodoroState::Run, now), true, now);
+        assert_eq!(timer.pomodoro_state, PomodoroState::Pause);
+        assert_eq!(remaining(&timer), 100);
+    }
+
+    #[test]
+    fn restores_a_phase_that_ended_while_closed_at_zero() {
+        let now = Local::now();
+        let mut timer = manual_timer();
+        let snapshot = focus_snapshot(&timer, PomodoroState::Run, now);
+        timer.restore(&snapshot, false, snapshot.saved_at + chrono::Duration::hours(1));
+        assert_eq!(remaining(&timer), 0);
+        assert!(timer.has_run_out());
+    }
+
+    #[test]
+    fn restores_a_waiting_phase_stopped() {
+        let now = Local::now();
+        let mut timer = manual_timer();
+        let snapshot = TimerSnapshot {
+            position: 1,
+            phase: PomodoroPhase::BeforeRelax,
+            ..focus_snapshot(&timer, PomodoroState::Run, now)
+        };
+        timer.restore(&snapshot, false, now);
+        assert_eq!((timer.position, timer.pomodoro_phase), (1, PomodoroPhase::BeforeRelax));
+        assert_eq!(timer.pomodoro_state, PomodoroState::Stop);
+        assert_eq!(remaining(&timer), 100);
+    }
+
+    #[test]
+    fn phase_end_sound_is_the_one_set_when_it_ends() {
+        let mut timer = manual_timer();
+        timer.pomodoro_phase = PomodoroPhase::Focus;
+        timer.start();
+        let started_with = timer.phase_end_sound().to_string();
+        let other = if timer.settings.get_end_of_focus_sound_for(Some(0)) == started_with { 1 } else { 0 };
+        timer.settings.update(SettingMessage::EndOfFocusSoundChanged(other));
+        timer.tick();
+        assert_ne!(timer.phase_end_sound(), started_with);
+        assert_eq!(timer.phase_end_sound(), timer.settings.get_end_of_focus_sound());
+    }
+
+    #[test]
+    fn restores_the_saved_intervals() {
+        let now = Local::now();
+        let mut saved = manual_timer();
+        saved.set_lengths(vec![PomodoroLength::new(60, 10)]);
+        let snapshot = saved.snapshot(now);
+        let mut timer = manual_timer();
+        timer.restore(&snapshot, false, now);
+        assert_eq!(timer.pomodoro_lengths, vec![PomodoroLength::new(60, 10)]);
+        // a config from before the intervals were saved keeps the current ones
+        let mut timer = manual_timer();
+        timer.restore_position(&TimerSnapshot { pomodoro_lengths: Vec::new(), ..snapshot });
+        assert_eq!(timer.pomodoro_lengths.len(), 3);
+    }
+
+    #[test]
+    fn restores_the_purist_mode_locked() {
+        let now = Local::now();
+        let mut saved = manual_timer();
+        saved.set_purist(true);
+        let snapshot = TimerSnapshot {
+            phase: PomodoroPhase::BeforeRelax,
+            // edited by hand, the mode keeps its intervals
+            pomodoro_lengths: vec![PomodoroLength::new(60, 60)],
+            ..saved.snapshot(now)
+        };
+        // and a lunch break turned on by hand stays off
+        let snapshot = TimerSnapshot {
+            settings: SavedSettings { lunch_break: true, ..snapshot.settings.clone() },
+            ..snapshot
+        };
+        let mut timer = manual_timer();
+        timer.restore_settings(&snapshot.settings);
+        timer.restore(&snapshot, false, now);
+        assert!(timer.settings.purist);
+        assert_eq!(timer.settings.skip_behavior, SkipBehavior::Discard);
+        assert!(!timer.settings.lunch_break);
+        assert_eq!(timer.pomodoro_lengths, PURIST_PRESET.lengths());
+        assert!(timer.keeps_break());
+    }
+}
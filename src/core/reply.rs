@@ -0,0 +1,25 @@
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Answers a request from outside the app, e.g. a control command, with a line of JSON, once.
+#[derive(Clone, Debug)]
+pub(crate) struct Reply(Arc<Mutex<Option<oneshot::Sender<String>>>>);
+
+impl Reply {
+    /// A reply and where its answer arrives, an error if the reply is dropped unanswered.
+    pub fn channel() -> (Self, oneshot::Receiver<String>) {
+        let (sender, receiver) = oneshot::channel();
+        (Self(Arc::new(Mutex::new(Some(sender)))), receiver)
+    }
+
+    pub fn send(&self, line: String) {
+        if let Some(sender) = self.0.lock().ok().and_then(|mut sender| sender.take()) {
+            _ = sender.send(line);
+        }
+    }
+
+    #[cfg_attr(not(feature = "control-socket"), allow(dead_code))]
+    pub fn ok(&self) {
+        self.send("{\"ok\":true}".to_string());
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Local};
+use crate::core::pomodoro_timer::PomodoroPhase;
+
+const CSV_HEADER: [&str; 10] = ["timer", "start_time", "phase", "planned_secs", "actual_secs", "completed", "note", "interruptions", "overtime_secs",
+    "distractions"];
+
+/// An interruption of a focus period, with what pulled away from it if it was noted.
+#[derive(Clone, Debug)]
+pub(crate) struct Distraction {
+    pub at: DateTime<Local>,
+    /// Empty if nothing was noted.
+    pub note: String,
+}
+
+/// A finished focus or relax phase.
+#[derive(Clone, Debug)]
+pub(crate) struct SessionRecord {
+    pub start: DateTime<Local>,
+    pub phase: PomodoroPhase,
+    pub planned_secs: u32,
+    pub actual_secs: u32,
+    pub completed: bool,
+    /// What a completed focus period was meant for, empty otherwise.
+    pub note: String,
+    /// Interruptions during a focus period, 0 for breaks.
+    pub interruptions: u32,
+    /// Seconds worked past the end of a focus period in overtime, not part of `actual_secs`.
+    pub overtime_secs: u32,
+    /// The interruptions with their times, empty for breaks.
+    pub distractions: Vec<Distraction>,
+}
+
+#[derive(Default)]
+pub(crate) struct SessionHistory {
+    pub records: Vec<SessionRecord>,
+}
+
+impl SessionHistory {
+    pub fn push(&mut self, record: SessionRecord) {
+        self.records.push(record);
+    }
+}
+
+/// Writes the histories of all timers as one CSV with the columns of `CSV_HEADER`, sorted by start.
+/// The timer column is the number of its tab, counting from 1. Empty histories result in the header only.
+/// The distractions of a record are one per line within their field, the time followed by the note.
+pub(crate) fn to_csv<'a>(histories: impl IntoIterator<Item=&'a SessionHistory>) -> String {
+    let mut records: Vec<(usize, &SessionRecord)> = histories.into_iter()
+        .enumerate()
+        .flat_map(|(index, history)| history.records.iter().map(move |record| (index + 1, record)))
+        .collect();
+    records.sort_by_key(|(_, record)| record.start);
+    let mut csv = String::new();
+    write_csv_line(&mut csv, CSV_HEADER.iter().map(|column| column.to_string()));
+    for (timer, record) in records {
+        write_csv_line(&mut csv, [
+            timer.to_string(),
+            record.start.to_rfc3339(),
+            format!("{:?}", record.phase),
+            record.planned_secs.to_string(),
+            record.actual_secs.to_string(),
+            record.completed.to_string(),
+            record.note.clone(),
+            record.interruptions.to_string(),
+            record.overtime_secs.to_string(),
+            record.distractions.iter()
+                .map(|distraction| format!("{} {}", distraction.at.to_rfc3339(), distraction.note).trim_end().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ].into_iter());
+    }
+    csv
+}
+
+fn write_csv_line(csv: &mut String, fields: impl Iterator<Item=String>) {
+    let fields = fields.map(|field| escape_csv_field(&field)).collect::<Vec<_>>();
+    csv.push_str(&fields.join(","));
+    csv.push_str("\r\n");
+}
+
+/// Quotes a field per RFC 4180 if it contains a separator, quote or line break.
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn record(start: DateTime<Local>, note: &str) -> SessionRecord {
+        SessionRecord {
+            start,
+            phase: PomodoroPhase::Focus,
+            planned_secs: 1500,
+            actual_secs: 1500,
+            completed: true,
+            note: note.to_string(),
+            interruptions: 0,
+            overtime_secs: 0,
+            distractions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn exports_all_timers_sorted_by_start() {
+        let at = |hour| Local.with_ymd_and_hms(2024, 5, 6, hour, 0, 0).unwrap();
+        let first = SessionHistory { records: vec![record(at(9), "first"), record(at(11), "third")] };
+        let second = SessionHistory { records: vec![record(at(10), "second")] };
+        let csv = to_csv([&first, &second]);
+        let lines: Vec<&str> = csv.split("\r\n").filter(|line| !line.is_empty()).collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("timer,start_time,"));
+        assert!(lines[1].starts_with(&format!("1,{}", at(9).to_rfc3339())));
+        assert!(lines[2].starts_with(&format!("2,{}", at(10).to_rfc3339())));
+        assert!(lines[3].starts_with(&format!("1,{}", at(11).to_rfc3339())));
+        assert!(lines[3].contains(",third,"));
+    }
+
+    #[test]
+    fn exports_the_header_without_records() {
+        assert_eq!(to_csv([&SessionHistory::default()]), format!("{}\r\n", CSV_HEADER.join(",")));
+    }
+}
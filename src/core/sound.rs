@@ -0,0 +1,32 @@
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+/// Time between the repetitions of a sound, so they don't run into each other.
+const REPEAT_SPACING: Duration = Duration::from_millis(1500);
+
+/// Plays a freedesktop sound without a notification, through libcanberra's player if it is installed.
+pub(crate) fn play(sound_id: &str) {
+    let result = Command::new("canberra-gtk-play")
+        .args(["--id", sound_id])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Err(error) = result {
+        tracing::warn!(sound_id, %error, "failed to play a sound");
+    }
+}
+
+/// Plays a sound `times` more times after it was played once, spaced out on a thread of their own.
+pub(crate) fn repeat(sound_id: &str, times: u8) {
+    if times == 0 {
+        return;
+    }
+    let sound_id = sound_id.to_string();
+    thread::spawn(move || {
+        for _ in 0..times {
+            thread::sleep(REPEAT_SPACING);
+            play(&sound_id);
+        }
+    });
+}
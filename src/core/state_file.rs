@@ -0,0 +1,58 @@
+//! The state of the active timer as a file for status bars like polybar or waybar to read.
+//!
+//! The file is `$XDG_RUNTIME_DIR/cosmic-pomodoro/state.json` and holds a single JSON object:
+//!
+//! ```json
+//! {"phase":"focus","state":"run","remaining_secs":1234,"initial_secs":1500}
+//! ```
+//!
+//! `phase` is one of `before-focus`, `focus`, `before-relax` and `relax`,
+//! `state` one of `stop`, `run` and `pause`.
+//! The file is replaced as a whole, so a reader never sees it half written.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::core::pomodoro_timer::{PomodoroPhase, PomodoroState};
+
+/// `None` without a runtime dir, the file isn't written then.
+fn path() -> Option<PathBuf> {
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR")?;
+    Some(PathBuf::from(runtime_dir).join("cosmic-pomodoro").join("state.json"))
+}
+
+pub(crate) fn write(phase: PomodoroPhase, state: PomodoroState, remaining_secs: u32, initial_secs: u32) -> io::Result<()> {
+    let Some(path) = path() else {
+        return Ok(());
+    };
+    let content = format!("{}\n", to_json(phase, state, remaining_secs, initial_secs));
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, content)?;
+    fs::rename(temporary, path)
+}
+
+/// The JSON object of the file, without a line break.
+pub(crate) fn to_json(phase: PomodoroPhase, state: PomodoroState, remaining_secs: u32, initial_secs: u32) -> String {
+    let phase = match phase {
+        PomodoroPhase::BeforeFocus => "before-focus",
+        PomodoroPhase::Focus => "focus",
+        PomodoroPhase::BeforeRelax => "before-relax",
+        PomodoroPhase::Relax => "relax",
+    };
+    let state = match state {
+        PomodoroState::Stop => "stop",
+        PomodoroState::Run => "run",
+        PomodoroState::Pause => "pause",
+    };
+    format!("{{\"phase\":\"{phase}\",\"state\":\"{state}\",\"remaining_secs\":{remaining_secs},\"initial_secs\":{initial_secs}}}")
+}
+
+/// Removes the file, e.g. on exit, so status bars don't show a stale timer.
+pub(crate) fn remove() {
+    if let Some(path) = path() {
+        _ = fs::remove_file(path);
+    }
+}
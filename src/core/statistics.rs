@@ -0,0 +1,51 @@
+use std::collections::BTreeMap;
+use chrono::{Days, NaiveDate};
+
+/// A count per local calendar day, e.g. of completed focus periods.
+pub(crate) type DailyCounts = BTreeMap<NaiveDate, u32>;
+
+fn goal_met(counts: &DailyCounts, goal: u32, day: NaiveDate) -> bool {
+    goal > 0 && counts.get(&day).is_some_and(|&count| count >= goal)
+}
+
+/// Consecutive days up to `today` on which the goal was met.
+/// Today only ends the streak once it is over, so an unmet goal today continues yesterday's streak.
+pub(crate) fn current_streak(counts: &DailyCounts, goal: u32, today: NaiveDate) -> u32 {
+    let mut day = if goal_met(counts, goal, today) {
+        today
+    } else {
+        match today.checked_sub_days(Days::new(1)) {
+            Some(yesterday) => yesterday,
+            None => return 0,
+        }
+    };
+    let mut streak = 0;
+    while goal_met(counts, goal, day) {
+        streak += 1;
+        match day.checked_sub_days(Days::new(1)) {
+            Some(previous) => day = previous,
+            None => break,
+        }
+    }
+    streak
+}
+
+/// The longest run of consecutive days on which the goal was met.
+pub(crate) fn longest_streak(counts: &DailyCounts, goal: u32) -> u32 {
+    let mut longest = 0;
+    let mut streak = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for (&day, &count) in counts {
+        if goal == 0 || count < goal {
+            streak = 0;
+            previous = None;
+            continue;
+        }
+        // a day without an entry breaks the streak
+        let consecutive = previous.and_then(|previous| previous.checked_add_days(Days::new(1))) == Some(day);
+        streak = if consecutive { streak + 1 } else { 1 };
+        previous = Some(day);
+        longest = longest.max(streak);
+    }
+    longest
+}
@@ -0,0 +1,104 @@
+//! A card of the day's statistics as a PNG image, for sharing the progress.
+//!
+//! The card is laid out as an SVG, like the progress ring, and rasterized with resvg.
+//! The text is drawn with the system fonts, a missing font leaves the text out rather than failing.
+
+use chrono::NaiveDate;
+use quick_xml::escape::escape;
+use resvg::{tiny_skia, usvg};
+use std::time::Duration;
+use crate::core::config::Config;
+use crate::core::duration_extension::TimeDurationExt;
+use crate::core::{localization, statistics};
+use crate::fl;
+
+/// Twice the size it's laid out in, so the text stays sharp when the image is scaled up for a post.
+const SCALE: f32 = 2.0;
+const WIDTH: u32 = 600;
+const HEIGHT: u32 = 315;
+const BACKGROUND_COLOR: &str = "#1b1b1b";
+const TEXT_COLOR: &str = "#f2f2f2";
+const DIM_TEXT_COLOR: &str = "#a6a6a6";
+/// The red of the tomato of the app icon.
+const ACCENT_COLOR: &str = "#d84141";
+
+/// The numbers of a day shown on the card, taken from the tracked statistics.
+#[derive(Clone, Debug)]
+pub(crate) struct SummaryCard {
+    pub day: NaiveDate,
+    pub completed: u32,
+    pub focus: Duration,
+    /// Consecutive days the daily goal was met, `None` without a goal.
+    pub streak: Option<u32>,
+}
+
+impl SummaryCard {
+    pub fn new(config: &Config, day: NaiveDate) -> Self {
+        Self {
+            day,
+            completed: config.daily_completed.get(&day).copied().unwrap_or(0),
+            focus: Duration::from_secs(config.daily_focus_secs.get(&day).copied().unwrap_or(0) as u64),
+            streak: (config.daily_goal > 0).then(|| statistics::current_streak(&config.daily_completed, config.daily_goal, day)),
+        }
+    }
+
+    /// Whether anything was tracked on the day, a card of zeros isn't worth sharing.
+    pub fn has_data(&self) -> bool {
+        self.completed > 0 || self.focus.as_minutes() > 0
+    }
+
+    /// The card as an SVG document, `WIDTH` by `HEIGHT`.
+    fn to_svg(&self) -> String {
+        let mut stats = vec![
+            (localization::format_number(self.completed as u64), fl!("summary-card", "completed", count = self.completed)),
+            (fl!("summary-card", "focus-time",
+                hours = self.focus.as_minutes() / 60,
+                minutes = self.focus.as_minutes() % 60),
+             fl!("summary-card", "focus")),
+        ];
+        if let Some(streak) = self.streak {
+            stats.push((localization::format_number(streak as u64), fl!("summary-card", "streak", days = streak)));
+        }
+        let column_width = WIDTH / stats.len() as u32;
+        let columns: String = stats.iter().enumerate()
+            .map(|(index, (value, label))| {
+                let x = column_width * index as u32 + column_width / 2;
+                format!(
+                    r#"<text x="{x}" y="180" font-size="48" font-weight="bold" fill="{TEXT_COLOR}" text-anchor="middle">{}</text>
+<text x="{x}" y="215" font-size="18" fill="{DIM_TEXT_COLOR}" text-anchor="middle">{}</text>
+"#,
+                    escape(value.as_str()),
+                    escape(label.as_str()),
+                )
+            })
+            .collect();
+        let day = localization::format_date(self.day);
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif">
+<rect width="{WIDTH}" height="{HEIGHT}" rx="24" fill="{BACKGROUND_COLOR}"/>
+<rect x="32" y="40" width="8" height="44" rx="4" fill="{ACCENT_COLOR}"/>
+<text x="56" y="68" font-size="28" font-weight="bold" fill="{TEXT_COLOR}">{}</text>
+<text x="56" y="92" font-size="16" fill="{DIM_TEXT_COLOR}">{}</text>
+{columns}<text x="{}" y="{}" font-size="14" fill="{DIM_TEXT_COLOR}" text-anchor="end">{}</text>
+</svg>"#,
+            escape(fl!("summary-card", "title").as_str()),
+            escape(day.as_str()),
+            WIDTH - 32,
+            HEIGHT - 28,
+            escape(fl!("app-title").as_str()),
+        )
+    }
+
+    /// Draws the card, ready to be written to a `.png` file.
+    /// Loads the system fonts, so it's better done off the UI thread.
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut options = usvg::Options::default();
+        options.fontdb_mut().load_system_fonts();
+        let tree = usvg::Tree::from_str(&self.to_svg(), &options).map_err(|e| e.to_string())?;
+        let (width, height) = ((WIDTH as f32 * SCALE) as u32, (HEIGHT as f32 * SCALE) as u32);
+        let mut pixmap = tiny_skia::Pixmap::new(width, height)
+            .ok_or_else(|| "the card has no size".to_string())?;
+        resvg::render(&tree, tiny_skia::Transform::from_scale(SCALE, SCALE), &mut pixmap.as_mut());
+        pixmap.encode_png().map_err(|e| e.to_string())
+    }
+}
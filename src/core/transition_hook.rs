@@ -0,0 +1,31 @@
+use crate::core::pomodoro_timer::PomodoroPhase;
+
+/// A platform specific cue on a phase transition, e.g. blinking a keyboard LED or a haptic pulse.
+///
+/// To add one, implement this trait and push it in [`registered_hooks`],
+/// behind a feature or a check for the hardware it needs.
+pub(crate) trait TransitionHook {
+    /// Called when a timer moved on from the phase `from` to `to` on its own.
+    /// Runs on the UI thread, so anything slow has to be spawned.
+    fn on_transition(&self, _from: PomodoroPhase, _to: PomodoroPhase) {}
+}
+
+/// Logs every transition at the debug level, to check when the hooks are called.
+struct LoggingHook;
+
+impl TransitionHook for LoggingHook {
+    fn on_transition(&self, from: PomodoroPhase, to: PomodoroPhase) {
+        tracing::debug!(?from, ?to, "phase transition");
+    }
+}
+
+/// The hooks called on a phase transition.
+/// The logging hook is turned on by setting `COSMIC_POMODORO_LOG_TRANSITIONS`,
+/// its output shows with `RUST_LOG=cosmic_pomodoro=debug`.
+pub(crate) fn registered_hooks() -> Vec<Box<dyn TransitionHook>> {
+    let mut hooks: Vec<Box<dyn TransitionHook>> = Vec::new();
+    if std::env::var_os("COSMIC_POMODORO_LOG_TRANSITIONS").is_some() {
+        hooks.push(Box::new(LoggingHook));
+    }
+    hooks
+}
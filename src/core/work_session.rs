@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+use std::time::{Duration, Instant};
+
+/// The time worked on a day, from its first started timer on.
+pub(crate) struct WorkSession {
+    pub day: NaiveDate,
+    started_at: Instant,
+    /// Time any timer was running until `last_update`.
+    running: Duration,
+    last_update: Instant,
+}
+
+impl WorkSession {
+    pub fn new(day: NaiveDate, now: Instant) -> Self {
+        Self {
+            day,
+            started_at: now,
+            running: Duration::ZERO,
+            last_update: now,
+        }
+    }
+
+    /// Counts the time since the last update as running if a timer was running in between.
+    pub fn update(&mut self, now: Instant, was_running: bool) {
+        if was_running {
+            self.running += now.saturating_duration_since(self.last_update);
+        }
+        self.last_update = now;
+    }
+
+    /// The wall-clock time since the start, or only the time a timer was running.
+    pub fn elapsed(&self, now: Instant, is_running: bool, wall_clock: bool) -> Duration {
+        if wall_clock {
+            return now.saturating_duration_since(self.started_at);
+        }
+        if is_running {
+            self.running + now.saturating_duration_since(self.last_update)
+        } else {
+            self.running
+        }
+    }
+}
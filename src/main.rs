@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use cosmic::iced;
-use app::CosmicPomodoro;
+use app::{CosmicPomodoro, Flags};
+use cosmic::Application;
+use crate::core::config::Config;
 /// The `app` module is used by convention to indicate the main component of our application.
 mod app;
 mod core;
@@ -13,7 +15,17 @@ mod views;
 /// - `()` is the flags that your app needs to use before it starts.
 ///  If your app does not need any flags, you can pass in `()`.
 fn main() -> cosmic::iced::Result {
+    // warnings and errors by default, more with e.g. RUST_LOG=cosmic_pomodoro=debug
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn"));
+    tracing_subscriber::fmt().with_env_filter(filter).init();
     let mut settings = cosmic::app::Settings::default();
     settings = settings.size(iced::Size::new(512.0, 768.0));
-    cosmic::app::run::<CosmicPomodoro>(settings, ())
+    let flags = Flags {
+        headless: std::env::args().skip(1).any(|arg| arg == "--headless"),
+    };
+    // closing the window only quits if the app doesn't keep running in the background
+    let (_, config) = Config::load(CosmicPomodoro::APP_ID);
+    settings = settings.exit_on_close(!config.run_in_background && !flags.headless);
+    cosmic::app::run_single_instance::<CosmicPomodoro>(settings, flags)
 }
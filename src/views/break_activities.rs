@@ -0,0 +1,28 @@
+use crate::app::Message;
+use crate::fl;
+use cosmic::iced::{Alignment, Color, Length};
+use cosmic::{theme, widget, Element};
+
+/// The editable list of activities suggested at the start of a break, headed in `phase_color`.
+pub fn get_break_activities_view(activities: &[String], phase_color: Color) -> Element<'_, Message> {
+    let mut root = widget::column()
+        .push(widget::text::title4(fl!("break-activities")).style(theme::Text::Color(phase_color)))
+        .spacing(10);
+    if activities.is_empty() {
+        root = root.push(widget::text::caption(fl!("break-activities", "empty")));
+    }
+    for (index, activity) in activities.iter().enumerate() {
+        root = root.push(widget::row::with_capacity(2)
+            .push(widget::text_input(fl!("break-activities", "placeholder"), activity)
+                .on_input(move |x| Message::BreakActivityChanged(index, x))
+                .width(Length::Fill))
+            .push(widget::button::text(fl!("break-activities", "delete"))
+                .on_press(Message::DeleteBreakActivity(index)))
+            .align_items(Alignment::Center)
+            .spacing(10)
+        );
+    }
+    root = root.push(widget::button::text(fl!("break-activities", "add"))
+        .on_press(Message::AddBreakActivity));
+    root.into()
+}
@@ -0,0 +1,47 @@
+use crate::app::Message;
+use crate::core::duration_extension::TimeDurationExt;
+use crate::core::localization;
+use crate::core::pomodoro_timer::PomodoroPhase;
+use crate::fl;
+use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::widget::container;
+use cosmic::iced::{Background, Color, Length};
+use cosmic::{cosmic_theme, theme, widget, Apply, Element};
+use std::time::Duration;
+
+/// Covers the screen during a break with its countdown.
+/// A flexible overlay can be skipped, a strict one stays up until the break is over.
+pub fn get_break_overlay_view<'a>(remaining_secs: u32, can_skip: bool) -> Element<'a, Message> {
+    let cosmic_theme::Spacing { space_m, .. } = theme::active().cosmic().spacing;
+    let remaining = localization::localize_digits(&Duration::from_secs(remaining_secs as u64).to_timer_string());
+
+    let mut root = widget::column()
+        .push(widget::text::title1(PomodoroPhase::Relax.localized_name()))
+        .push(widget::text::heading(remaining).size(48))
+        .align_items(cosmic::iced::Alignment::Center)
+        .spacing(space_m);
+    if can_skip {
+        root = root.push(widget::button::standard(fl!("skip")).on_press(Message::SkipPhase));
+    }
+    root.apply(widget::container)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(Horizontal::Center)
+        .align_y(Vertical::Center)
+        .style(theme::Container::Background)
+        .into()
+}
+
+/// Darkens the screen by `opacity` percent during a break, to encourage looking away.
+pub fn get_dim_view<'a>(opacity: u32) -> Element<'a, Message> {
+    let color = Color::from_rgba(0.0, 0.0, 0.0, opacity as f32 / 100.0);
+    widget::Space::new(Length::Fill, Length::Fill)
+        .apply(widget::container)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::Container::custom(move |_| container::Appearance {
+            background: Some(Background::Color(color)),
+            ..Default::default()
+        }))
+        .into()
+}
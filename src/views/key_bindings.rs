@@ -0,0 +1,33 @@
+use crate::app::Message;
+use crate::core::key_bindings::{BindableAction, KeyBindings};
+use crate::fl;
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::Color;
+use cosmic::{theme, widget, Element};
+
+/// Lists the bindable actions with their keys, a button starts capturing the next key press.
+/// The heading is in `phase_color`, like the other settings headings.
+pub fn get_key_bindings_view<'a>(key_bindings: &'a KeyBindings, capturing: Option<BindableAction>, conflict: Option<&'a str>,
+                                 phase_color: Color) -> Element<'a, Message> {
+    let mut root = widget::column()
+        .push(widget::text::title4(fl!("key-bindings")).style(theme::Text::Color(phase_color)))
+        .spacing(10);
+    for action in BindableAction::ALL {
+        let label = if capturing == Some(action) {
+            fl!("key-bindings", "capturing")
+        } else {
+            key_bindings.get(&action)
+                .and_then(|stored| stored.to_key_bind())
+                .map_or_else(|| fl!("key-bindings", "unbound"), |key_bind| key_bind.to_string())
+        };
+        root = root.push(widget::row::with_capacity(2)
+            .push(widget::text::text(action.localized_name()).vertical_alignment(Vertical::Center))
+            .push(widget::button::standard(label).on_press(Message::CaptureKeyBind(action)))
+            .spacing(10)
+        );
+    }
+    if let Some(conflict) = conflict {
+        root = root.push(widget::text::text(conflict).style(theme::Text::Accent));
+    }
+    root.into()
+}
@@ -1 +1,7 @@
-pub mod settings;
\ No newline at end of file
+pub mod break_activities;
+#[cfg(feature = "wayland")]
+pub mod break_overlay;
+pub mod key_bindings;
+pub mod settings;
+pub mod statistics;
+pub mod timeline;
\ No newline at end of file
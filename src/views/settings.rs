@@ -1,70 +1,1112 @@
 use crate::app::Message;
+use crate::core::duration_string;
+use crate::core::pomodoro_timer::PomodoroLength;
 use crate::fl;
+use chrono::NaiveTime;
+use std::ops::RangeInclusive;
 use cosmic::iced::alignment::Vertical;
-use cosmic::{widget, Element};
+use cosmic::iced::Color;
+use cosmic::{theme, widget, Element};
+use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter, IntoEnumIterator};
 
 pub(crate) struct Settings {
-    end_of_focus_sound: usize,
-    end_of_relax_sound: usize,
+    /// Index into `sound_names`, `None` if nothing is selected.
+    end_of_focus_sound: Option<usize>,
+    end_of_relax_sound: Option<usize>,
     sound_names: Vec<String>,
+    /// `sound_names` after an entry for the sound of the settings, for the interval overrides.
+    override_sound_names: Vec<String>,
+    /// The freedesktop sound ids of `sound_names`, as played.
+    sound_ids: Vec<String>,
+    sound_filter: String,
+    /// Narrows the shown settings down to the ones with this in their label.
+    search: String,
+    /// Indices into `sound_names` matching `sound_filter`.
+    filtered_sounds: Vec<usize>,
+    filtered_sound_names: Vec<String>,
+    /// Starts the break right away if the window is focused when a focus period ends.
+    pub auto_start_break_when_focused: bool,
+    /// Keeps a focus period running past its end if the break doesn't start on its own, until it's moved on by hand.
+    pub overtime: bool,
+    /// Pauses a focus period while the window is unfocused.
+    pub pause_on_unfocus: bool,
+    /// Seconds the window can be unfocused before `pause_on_unfocus` pauses, so reading or thinking elsewhere doesn't.
+    pub unfocus_grace_secs: u32,
+    /// A running focus period can't be paused, skipped or reset until it's over.
+    pub focus_lock: bool,
+    /// The arrow keys change the length of the interval too, not only of the phase waiting to be started.
+    pub adjust_keeps_interval: bool,
+    /// Keeps to the rules of the original technique, see [`Settings::apply_purist`].
+    pub purist: bool,
+    pub skip_behavior: SkipBehavior,
+    skip_behavior_names: Vec<String>,
+    notification_mode_names: Vec<String>,
+    focus_intensity_names: Vec<String>,
+    /// Remaining seconds of a focus period below which the timer is highlighted.
+    pub warning_threshold: u32,
+    /// Seconds between reminders to e.g. drink water during a focus period, 0 to turn them off.
+    pub micro_reminder_interval_secs: u32,
+    /// Seconds between prompts while waiting for the next phase to be started, 0 to turn them off.
+    pub idle_prompt_secs: u32,
+    pub show_clock: bool,
+    pub layout: Layout,
+    layout_names: Vec<String>,
+    /// Only affects the remaining time label, the ring keeps full precision.
+    pub display_granularity: DisplayGranularity,
+    /// Shows the last minute of a focus period as a big number of seconds.
+    pub final_countdown: bool,
+    display_granularity_names: Vec<String>,
+    /// Replaces a break starting within the lunch window with the lunch break length.
+    pub lunch_break: bool,
+    lunch_start: NaiveTime,
+    lunch_start_input: String,
+    lunch_end: NaiveTime,
+    lunch_end_input: String,
+    pub lunch_break_length: u32,
+    /// Mutes the sounds at the end of phases within the quiet hours, e.g. late at night.
+    pub quiet_hours: bool,
+    quiet_start: NaiveTime,
+    quiet_start_input: String,
+    quiet_end: NaiveTime,
+    quiet_end_input: String,
+    /// Pauses a phase that starts on its own within the quiet hours.
+    pub quiet_pause: bool,
+    /// Still shows the notifications within the quiet hours, just without sound.
+    pub quiet_notifications: bool,
+    /// Goes from one focus period straight to the next, without breaks.
+    pub continuous_focus: bool,
+    pub long_break_trigger: LongBreakTrigger,
+    long_break_trigger_names: Vec<String>,
+    /// Focus minutes after which a break is a long one, for [`LongBreakTrigger::FocusTime`].
+    pub long_break_focus_mins: u32,
+    /// The phase a new or reset timer starts with.
+    pub start_phase: StartPhase,
+    start_phase_names: Vec<String>,
+    /// The overlay needs the layer shell.
+    #[cfg(feature = "wayland")]
+    pub break_overlay: BreakOverlay,
+    /// The interval whose last entered length was out of `LENGTH_RANGE` and couldn't be used as it was.
+    pub length_error: Option<usize>,
+    /// How dark [`BreakOverlay::Dim`] makes the screen, in percent.
+    #[cfg(feature = "wayland")]
+    pub dim_opacity: u32,
+    pub progress_style: ProgressStyle,
+    progress_style_names: Vec<String>,
+    /// Stroke width of the progress ring, in icon units.
+    pub ring_stroke_width: u32,
+    /// Size of the progress ring in percent, limited so the ring stays inside the button.
+    pub ring_size: u32,
+    /// Slows the progress ring down towards the end of a phase instead of filling it linearly.
+    pub ease_ring: bool,
+    /// Celebrates once all intervals are done and the cycle starts over.
+    pub celebrate_cycle: bool,
+    /// How often the sound at the end of a phase plays, see `SOUND_REPEAT_RANGE`.
+    pub sound_repeat: u8,
+    /// Lets the ring of a paused timer breathe, which needs regular redraws.
+    pub pulse_when_paused: bool,
+    #[cfg(feature = "wayland")]
+    break_overlay_names: Vec<String>,
 }
 
+pub(crate) const TIME_FORMAT: &str = "%H:%M";
+const RING_STROKE_WIDTH_RANGE: RangeInclusive<u32> = 1..=60;
+const RING_SIZE_RANGE: RangeInclusive<u32> = 20..=100;
+/// More than three times gets annoying rather than harder to miss.
+const SOUND_REPEAT_RANGE: RangeInclusive<u8> = 1..=3;
+/// Dimming the screen completely would hide what's on it, which is what the strict overlay is for.
+#[cfg(feature = "wayland")]
+const DIM_OPACITY_RANGE: RangeInclusive<u32> = 5..=80;
 
 impl Settings {
     pub fn new() -> Self {
+        let lunch_start = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let lunch_end = NaiveTime::from_hms_opt(13, 0, 0).unwrap();
+        let quiet_start = NaiveTime::from_hms_opt(22, 0, 0).unwrap();
+        let quiet_end = NaiveTime::from_hms_opt(7, 0, 0).unwrap();
+        let sound_names: Vec<String> = SoundName::iter().map(|x| x.to_string()).collect();
+        let find_sound = |sound: SoundName| SoundName::iter().position(|x| x == sound);
         Self {
-            end_of_focus_sound: 0,
-            end_of_relax_sound: 0,
-            sound_names: SoundName::iter().map(|x| x.to_string()).collect(),
+            end_of_focus_sound: find_sound(SoundName::WindowAttentionInactive),
+            end_of_relax_sound: find_sound(SoundName::AlarmClockElapsed),
+            sound_ids: sound_names.iter().map(|name| to_sound_id(name)).collect(),
+            override_sound_names: std::iter::once(fl!("intervals", "default-sound"))
+                .chain(sound_names.iter().cloned())
+                .collect(),
+            sound_filter: String::new(),
+            search: String::new(),
+            filtered_sounds: (0..sound_names.len()).collect(),
+            filtered_sound_names: sound_names.clone(),
+            sound_names,
+            auto_start_break_when_focused: true,
+            overtime: false,
+            pause_on_unfocus: false,
+            unfocus_grace_secs: 180,
+            focus_lock: false,
+            adjust_keeps_interval: false,
+            purist: false,
+            skip_behavior: SkipBehavior::Discard,
+            skip_behavior_names: SkipBehavior::ALL.iter().map(|x| x.localized_name()).collect(),
+            notification_mode_names: NotificationMode::ALL.iter().map(|x| x.localized_name()).collect(),
+            focus_intensity_names: FocusIntensity::ALL.iter().map(|x| x.localized_name()).collect(),
+            warning_threshold: 60,
+            micro_reminder_interval_secs: 0,
+            idle_prompt_secs: 0,
+            show_clock: false,
+            layout: Layout::Automatic,
+            layout_names: Layout::ALL.iter().map(|x| x.localized_name()).collect(),
+            display_granularity: DisplayGranularity::Seconds,
+            final_countdown: false,
+            display_granularity_names: DisplayGranularity::ALL.iter().map(|x| x.localized_name()).collect(),
+            lunch_break: false,
+            lunch_start,
+            lunch_start_input: lunch_start.format(TIME_FORMAT).to_string(),
+            lunch_end,
+            lunch_end_input: lunch_end.format(TIME_FORMAT).to_string(),
+            lunch_break_length: 30 * 60,
+            quiet_hours: false,
+            quiet_start,
+            quiet_start_input: quiet_start.format(TIME_FORMAT).to_string(),
+            quiet_end,
+            quiet_end_input: quiet_end.format(TIME_FORMAT).to_string(),
+            quiet_pause: false,
+            quiet_notifications: true,
+            continuous_focus: false,
+            long_break_trigger: LongBreakTrigger::Intervals,
+            long_break_trigger_names: LongBreakTrigger::ALL.iter().map(|x| x.localized_name()).collect(),
+            long_break_focus_mins: 100,
+            start_phase: StartPhase::Focus,
+            start_phase_names: StartPhase::ALL.iter().map(|x| x.localized_name()).collect(),
+            #[cfg(feature = "wayland")]
+            break_overlay: BreakOverlay::Off,
+            length_error: None,
+            #[cfg(feature = "wayland")]
+            dim_opacity: 30,
+            progress_style: ProgressStyle::Ring,
+            progress_style_names: ProgressStyle::ALL.iter().map(|x| x.localized_name()).collect(),
+            ring_stroke_width: 10,
+            ring_size: 100,
+            ease_ring: false,
+            celebrate_cycle: true,
+            sound_repeat: 1,
+            pulse_when_paused: false,
+            #[cfg(feature = "wayland")]
+            break_overlay_names: BreakOverlay::ALL.iter().map(|x| x.localized_name()).collect(),
         }
     }
-    pub fn get_end_of_focus_sound(&self) -> &str { &self.sound_names[self.end_of_focus_sound] }
-    pub fn get_end_of_relax_sound(&self) -> &str { &self.sound_names[self.end_of_relax_sound] }
+    /// Turns off whatever goes against the original technique: a long break every fourth pomodoro, starting with focus,
+    /// no lunch break, no overtime and no skipped time carried over.
+    /// The intervals are set by the timer, see [`PURIST_PRESET`].
+    pub fn apply_purist(&mut self) {
+        self.continuous_focus = false;
+        self.long_break_trigger = LongBreakTrigger::Intervals;
+        self.start_phase = StartPhase::Focus;
+        self.lunch_break = false;
+        self.overtime = false;
+        self.skip_behavior = SkipBehavior::Discard;
+    }
+    pub fn get_end_of_focus_sound(&self) -> &str { self.get_sound_id(self.end_of_focus_sound) }
+    pub fn get_end_of_relax_sound(&self) -> &str { self.get_sound_id(self.end_of_relax_sound) }
+    /// The sound of an interval, falling back to the end of focus sound if it has none.
+    pub fn get_end_of_focus_sound_for(&self, sound: Option<usize>) -> &str { self.get_sound_id(sound.or(self.end_of_focus_sound)) }
+    pub fn get_end_of_relax_sound_for(&self, sound: Option<usize>) -> &str { self.get_sound_id(sound.or(self.end_of_relax_sound)) }
+
+    /// The freedesktop sound id to play.
+    /// Falls back to the first sound if nothing or an unknown sound is selected.
+    fn get_sound_id(&self, sound: Option<usize>) -> &str {
+        sound.and_then(|index| self.sound_ids.get(index))
+            .or(self.sound_ids.first())
+            .map_or("", String::as_str)
+    }
+
+    fn apply_sound_filter(&mut self) {
+        let filter = self.sound_filter.to_lowercase();
+        self.filtered_sounds = self.sound_names.iter()
+            .enumerate()
+            .filter(|(_, name)| name.to_lowercase().contains(&filter))
+            .map(|(index, _)| index)
+            .collect();
+        self.filtered_sound_names = self.filtered_sounds.iter()
+            .map(|&index| self.sound_names[index].clone())
+            .collect();
+    }
+
+    /// Maps a selected sound to its position in the filtered list.
+    fn filtered_selection(&self, sound: Option<usize>) -> Option<usize> {
+        self.filtered_sounds.iter().position(|&index| Some(index) == sound)
+    }
 
-    pub fn get_settings_view(&self) -> Element<Message> {
-        let title = widget::text::title3(fl!("settings"));
+    /// Whether a break starting at `time` should become a lunch break.
+    pub fn is_lunch_time(&self, time: NaiveTime) -> bool {
+        self.lunch_break && is_within(self.lunch_start, self.lunch_end, time)
+    }
+
+    /// Whether `time` is within the quiet hours, if they are turned on.
+    pub fn is_quiet_time(&self, time: NaiveTime) -> bool {
+        self.quiet_hours && is_within(self.quiet_start, self.quiet_end, time)
+    }
+
+    /// Changes take effect right away, also for the running phase, e.g. a new sound is played at its end.
+    /// `notification_mode` is an app wide setting, changes to it are handled by the app.
+    /// The headings are in `phase_color`, the color of the timer's current phase.
+    pub fn get_settings_view<'a>(&'a self, pomodoro_lengths: &'a [PomodoroLength], can_undo_delete: bool, notification_mode: NotificationMode,
+                                 phase_color: Color) -> Element<'a, Message> {
+        let title = widget::text::title3(fl!("settings")).style(theme::Text::Color(phase_color));
+
+        let search = widget::text_input(fl!("settings", "search-placeholder"), &self.search)
+            .on_input(|x| Message::ChangeSetting(SettingMessage::SearchChanged(x)));
+
+        let mut root = widget::column().push(title).push(search);
+        let mut settings: Vec<(String, Element<'a, Message>)> = Vec::new();
+        //SoundFilter
+        let input = widget::text_input(fl!("settings","sound-filter-placeholder"), &self.sound_filter)
+            .on_input(|x| Message::ChangeSetting(SettingMessage::SoundFilterChanged(x)));
+        settings.push((fl!("settings","sound-filter"), input.into()));
 
-        let mut root = widget::column().push(title);
-        let mut settings = Vec::new();
         //EndOfFocusSound
-        let selection = Some(self.end_of_focus_sound);
-        let dropdown = widget::dropdown(&self.sound_names, selection, |x| Message::ChangeSetting(SettingMessage::EndOfFocusSoundChanged(x)));
-        settings.push((fl!("settings","end-of-focus-sound"), dropdown));
+        let selection = self.filtered_selection(self.end_of_focus_sound);
+        let filtered_sounds = self.filtered_sounds.clone();
+        let dropdown = widget::dropdown(&self.filtered_sound_names, selection, move |x| Message::ChangeSetting(SettingMessage::EndOfFocusSoundChanged(filtered_sounds[x])));
+        settings.push((fl!("settings","end-of-focus-sound"), Self::with_sound_id(dropdown.into(), self.get_end_of_focus_sound())));
+
+        //SoundRepeat
+        let slider = widget::slider(SOUND_REPEAT_RANGE, self.sound_repeat, |x| Message::ChangeSetting(SettingMessage::SoundRepeatChanged(x)));
+        settings.push((fl!("settings","sound-repeat", count = self.sound_repeat), slider.into()));
+
+        //NotificationMode
+        let selection = NotificationMode::ALL.iter().position(|&x| x == notification_mode);
+        let dropdown = widget::dropdown(&self.notification_mode_names, selection, |x| Message::ChangeSetting(SettingMessage::NotificationModeChanged(NotificationMode::ALL[x])));
+        settings.push((fl!("settings","notification-mode"), dropdown.into()));
+
+        //PauseOnUnfocus
+        let toggler = widget::toggler(None, self.pause_on_unfocus, |x| Message::ChangeSetting(SettingMessage::PauseOnUnfocusChanged(x)));
+        settings.push((fl!("settings","pause-on-unfocus"), toggler.into()));
+        if self.pause_on_unfocus {
+            let input = widget::text_input(fl!("settings","seconds-placeholder"), self.unfocus_grace_secs.to_string())
+                .on_input(|x| Message::ChangeSetting(SettingMessage::UnfocusGraceChanged(x)));
+            settings.push((fl!("settings","unfocus-grace"), input.into()));
+        }
+
+        //FocusLock
+        let toggler = widget::toggler(None, self.focus_lock, |x| Message::ChangeSetting(SettingMessage::FocusLockChanged(x)));
+        settings.push((fl!("settings","focus-lock"), toggler.into()));
+
+        //AdjustKeepsInterval
+        let toggler = widget::toggler(None, self.adjust_keeps_interval, |x| Message::ChangeSetting(SettingMessage::AdjustKeepsIntervalChanged(x)));
+        settings.push((fl!("settings","adjust-keeps-interval"), toggler.into()));
+
+        //Purist
+        let toggler = widget::toggler(None, self.purist, |x| Message::ChangeSetting(SettingMessage::PuristChanged(x)));
+        // the settings it fixes are greyed out, so it says what it does
+        let purist: Element<Message> = if self.purist {
+            widget::column::with_capacity(2)
+                .push(toggler)
+                .push(widget::text::caption(fl!("settings","purist-explanation")))
+                .spacing(5)
+                .into()
+        } else {
+            toggler.into()
+        };
+        settings.push((fl!("settings","purist"), purist));
+
+        //ContinuousFocus
+        let toggler = widget::toggler(None, self.continuous_focus, |x| Message::ChangeSetting(SettingMessage::ContinuousFocusChanged(x)));
+        settings.push((fl!("settings","continuous-focus"), self.unless_purist(toggler.into(), fl!("settings","off"))));
+
+        // the break settings have no effect without breaks
+        if !self.continuous_focus {
+            //EndOfRelaxSound
+            let selection = self.filtered_selection(self.end_of_relax_sound);
+            let filtered_sounds = self.filtered_sounds.clone();
+            let dropdown = widget::dropdown(&self.filtered_sound_names, selection, move |x| Message::ChangeSetting(SettingMessage::EndOfRelaxSoundChanged(filtered_sounds[x])));
+            settings.push((fl!("settings","end-of-relax-sound"), Self::with_sound_id(dropdown.into(), self.get_end_of_relax_sound())));
+
+            //LongBreakTrigger
+            let selection = LongBreakTrigger::ALL.iter().position(|&x| x == self.long_break_trigger);
+            let dropdown = widget::dropdown(&self.long_break_trigger_names, selection, |x| Message::ChangeSetting(SettingMessage::LongBreakTriggerChanged(LongBreakTrigger::ALL[x])));
+            settings.push((fl!("settings","long-break-trigger"), self.unless_purist(dropdown.into(), self.long_break_trigger.localized_name())));
+            if self.long_break_trigger == LongBreakTrigger::FocusTime {
+                let input = widget::text_input("", self.long_break_focus_mins.to_string())
+                    .on_input(|x| Message::ChangeSetting(SettingMessage::LongBreakFocusChanged(x)));
+                settings.push((fl!("settings","long-break-focus"), input.into()));
+            }
+
+            //StartPhase
+            let selection = StartPhase::ALL.iter().position(|&x| x == self.start_phase);
+            let dropdown = widget::dropdown(&self.start_phase_names, selection, |x| Message::ChangeSetting(SettingMessage::StartPhaseChanged(StartPhase::ALL[x])));
+            settings.push((fl!("settings","start-phase"), self.unless_purist(dropdown.into(), self.start_phase.localized_name())));
+
+            //AutoStartBreakWhenFocused
+            let toggler = widget::toggler(None, self.auto_start_break_when_focused, |x| Message::ChangeSetting(SettingMessage::AutoStartBreakWhenFocusedChanged(x)));
+            settings.push((fl!("settings","auto-start-break-when-focused"), toggler.into()));
+
+            //Overtime
+            let toggler = widget::toggler(None, self.overtime, |x| Message::ChangeSetting(SettingMessage::OvertimeChanged(x)));
+            settings.push((fl!("settings","overtime"), self.unless_purist(toggler.into(), fl!("settings","off"))));
+
+            //BreakOverlay
+            #[cfg(feature = "wayland")]
+            {
+                let selection = BreakOverlay::ALL.iter().position(|&x| x == self.break_overlay);
+                let dropdown = widget::dropdown(&self.break_overlay_names, selection, |x| Message::ChangeSetting(SettingMessage::BreakOverlayChanged(BreakOverlay::ALL[x])));
+                settings.push((fl!("settings","break-overlay"), dropdown.into()));
 
-        //EndOfRelaxSound
-        let selection = Some(self.end_of_relax_sound);
-        let dropdown = widget::dropdown(&self.sound_names, selection, |x| Message::ChangeSetting(SettingMessage::EndOfRelaxSoundChanged(x)));
-        settings.push((fl!("settings","end-of-relax-sound"), dropdown));
+                //DimOpacity
+                if self.break_overlay == BreakOverlay::Dim {
+                    let slider = widget::slider(DIM_OPACITY_RANGE, self.dim_opacity, |x| Message::ChangeSetting(SettingMessage::DimOpacityChanged(x)));
+                    settings.push((fl!("settings","dim-opacity"), slider.into()));
+                }
+            }
+        }
+
+        //SkipBehavior
+        let selection = SkipBehavior::ALL.iter().position(|&x| x == self.skip_behavior);
+        let dropdown = widget::dropdown(&self.skip_behavior_names, selection, |x| Message::ChangeSetting(SettingMessage::SkipBehaviorChanged(SkipBehavior::ALL[x])));
+        settings.push((fl!("settings","skip-behavior"), self.unless_purist(dropdown.into(), self.skip_behavior.localized_name())));
+
+        //WarningThreshold
+        let input = widget::text_input(fl!("settings","seconds-placeholder"), self.warning_threshold.to_string())
+            .on_input(|x| Message::ChangeSetting(SettingMessage::WarningThresholdChanged(x)));
+        settings.push((fl!("settings","warning-threshold"), input.into()));
+
+        //MicroReminderInterval
+        let input = widget::text_input(fl!("settings","seconds-placeholder"), self.micro_reminder_interval_secs.to_string())
+            .on_input(|x| Message::ChangeSetting(SettingMessage::MicroReminderIntervalChanged(x)));
+        settings.push((fl!("settings","micro-reminder-interval"), input.into()));
+
+        //IdlePrompt
+        let input = widget::text_input(fl!("settings","seconds-placeholder"), self.idle_prompt_secs.to_string())
+            .on_input(|x| Message::ChangeSetting(SettingMessage::IdlePromptChanged(x)));
+        settings.push((fl!("settings","idle-prompt"), input.into()));
+
+        //ShowClock
+        let toggler = widget::toggler(None, self.show_clock, |x| Message::ChangeSetting(SettingMessage::ShowClockChanged(x)));
+        settings.push((fl!("settings","show-clock"), toggler.into()));
+
+        //Layout
+        let selection = Layout::ALL.iter().position(|&x| x == self.layout);
+        let dropdown = widget::dropdown(&self.layout_names, selection, |x| Message::ChangeSetting(SettingMessage::LayoutChanged(Layout::ALL[x])));
+        settings.push((fl!("settings","layout"), dropdown.into()));
+
+        //DisplayGranularity
+        let selection = DisplayGranularity::ALL.iter().position(|&x| x == self.display_granularity);
+        let dropdown = widget::dropdown(&self.display_granularity_names, selection, |x| Message::ChangeSetting(SettingMessage::DisplayGranularityChanged(DisplayGranularity::ALL[x])));
+        settings.push((fl!("settings","display-granularity"), dropdown.into()));
+
+        //FinalCountdown
+        let toggler = widget::toggler(None, self.final_countdown, |x| Message::ChangeSetting(SettingMessage::FinalCountdownChanged(x)));
+        settings.push((fl!("settings","final-countdown"), toggler.into()));
+
+        //ProgressStyle
+        let selection = ProgressStyle::ALL.iter().position(|&x| x == self.progress_style);
+        let dropdown = widget::dropdown(&self.progress_style_names, selection, |x| Message::ChangeSetting(SettingMessage::ProgressStyleChanged(ProgressStyle::ALL[x])));
+        settings.push((fl!("settings","progress-style"), dropdown.into()));
+
+        //RingStrokeWidth
+        let slider = widget::slider(RING_STROKE_WIDTH_RANGE, self.ring_stroke_width, |x| Message::ChangeSetting(SettingMessage::RingStrokeWidthChanged(x)));
+        settings.push((fl!("settings","ring-stroke-width"), slider.into()));
+
+        //RingSize
+        let slider = widget::slider(RING_SIZE_RANGE, self.ring_size, |x| Message::ChangeSetting(SettingMessage::RingSizeChanged(x)));
+        settings.push((fl!("settings","ring-size"), slider.into()));
+
+        //EaseRing
+        let toggler = widget::toggler(None, self.ease_ring, |x| Message::ChangeSetting(SettingMessage::EaseRingChanged(x)));
+        settings.push((fl!("settings","ease-ring"), toggler.into()));
 
-        for (setting_name, dropdown) in settings {
+        //CelebrateCycle
+        let toggler = widget::toggler(None, self.celebrate_cycle, |x| Message::ChangeSetting(SettingMessage::CelebrateCycleChanged(x)));
+        settings.push((fl!("settings","celebrate-cycle"), toggler.into()));
+
+        //PulseWhenPaused
+        let toggler = widget::toggler(None, self.pulse_when_paused, |x| Message::ChangeSetting(SettingMessage::PulseWhenPausedChanged(x)));
+        settings.push((fl!("settings","pulse-when-paused"), toggler.into()));
+
+        //LunchBreak
+        if !self.continuous_focus {
+            let toggler = widget::toggler(None, self.lunch_break, |x| Message::ChangeSetting(SettingMessage::LunchBreakChanged(x)));
+            settings.push((fl!("settings","lunch-break"), self.unless_purist(toggler.into(), fl!("settings","off"))));
+        }
+        if self.lunch_break && !self.continuous_focus {
+            let input = widget::text_input(TIME_FORMAT, &self.lunch_start_input)
+                .on_input(|x| Message::ChangeSetting(SettingMessage::LunchStartChanged(x)));
+            settings.push((fl!("settings","lunch-start"), input.into()));
+            let input = widget::text_input(TIME_FORMAT, &self.lunch_end_input)
+                .on_input(|x| Message::ChangeSetting(SettingMessage::LunchEndChanged(x)));
+            settings.push((fl!("settings","lunch-end"), input.into()));
+            let input = widget::text_input(fl!("intervals", "relax"), self.lunch_break_length.to_string())
+                .on_input(|x| Message::ChangeSetting(SettingMessage::LunchBreakLengthChanged(x)));
+            settings.push((fl!("settings","lunch-break-length"), input.into()));
+        }
+
+        //QuietHours
+        let toggler = widget::toggler(None, self.quiet_hours, |x| Message::ChangeSetting(SettingMessage::QuietHoursChanged(x)));
+        settings.push((fl!("settings","quiet-hours"), toggler.into()));
+        if self.quiet_hours {
+            let input = widget::text_input(TIME_FORMAT, &self.quiet_start_input)
+                .on_input(|x| Message::ChangeSetting(SettingMessage::QuietStartChanged(x)));
+            settings.push((fl!("settings","quiet-start"), input.into()));
+            let input = widget::text_input(TIME_FORMAT, &self.quiet_end_input)
+                .on_input(|x| Message::ChangeSetting(SettingMessage::QuietEndChanged(x)));
+            settings.push((fl!("settings","quiet-end"), input.into()));
+            let toggler = widget::toggler(None, self.quiet_pause, |x| Message::ChangeSetting(SettingMessage::QuietPauseChanged(x)));
+            settings.push((fl!("settings","quiet-pause"), toggler.into()));
+            let toggler = widget::toggler(None, self.quiet_notifications, |x| Message::ChangeSetting(SettingMessage::QuietNotificationsChanged(x)));
+            settings.push((fl!("settings","quiet-notifications"), toggler.into()));
+        }
+
+        let search = self.search.trim().to_lowercase();
+        settings.retain(|(setting_name, _)| setting_name.to_lowercase().contains(&search));
+        for (setting_name, setting) in settings {
             root = root.push(widget::row::with_capacity(2)
                 .push(widget::text::text(setting_name).vertical_alignment(Vertical::Center))
-                .push(dropdown)
+                .push(setting)
                 .spacing(10)
                 );
         }
+        // the intervals are found by their title
+        if fl!("intervals").to_lowercase().contains(&search) || fl!("focus-intensity").to_lowercase().contains(&search) {
+            root = root.push(self.get_intervals_view(pomodoro_lengths, can_undo_delete, phase_color));
+        }
         root.into()
     }
 
+    /// A setting the purist mode fixes is greyed out, showing its `fixed_value` instead of `setting`.
+    fn unless_purist<'a>(&self, setting: Element<'a, Message>, fixed_value: String) -> Element<'a, Message> {
+        if self.purist {
+            // without a message the input is disabled, like the fixed interval lengths
+            widget::text_input("", fixed_value).into()
+        } else {
+            setting
+        }
+    }
+
+    /// Shows the sound id that is actually played next to a sound dropdown.
+    fn with_sound_id<'a>(dropdown: Element<'a, Message>, sound_id: &'a str) -> Element<'a, Message> {
+        widget::row::with_capacity(2)
+            .push(dropdown)
+            .push(widget::text::caption(sound_id).vertical_alignment(Vertical::Center))
+            .spacing(10)
+            .into()
+    }
+
+    fn get_intervals_view<'a>(&'a self, pomodoro_lengths: &'a [PomodoroLength], can_undo_delete: bool, phase_color: Color) -> Element<'a, Message> {
+        let mut intervals = widget::column()
+            .push(widget::text::title4(fl!("intervals")).style(theme::Text::Color(phase_color)))
+            .spacing(10);
+        // the purist mode fixes the lengths, they are shown but can't be edited
+        let editable = !self.purist;
+        if editable {
+            // nothing is selected once the intervals were edited apart from the presets
+            let selection = FocusIntensity::ALL.iter().position(|x| x.matches(pomodoro_lengths));
+            let presets = widget::dropdown(&self.focus_intensity_names, selection, |x| Message::ChangeSetting(SettingMessage::FocusIntensityChanged(FocusIntensity::ALL[x])));
+            intervals = intervals.push(widget::row::with_capacity(2)
+                .push(widget::text::text(fl!("focus-intensity")).vertical_alignment(Vertical::Center))
+                .push(presets)
+                .spacing(10));
+        }
+        for (index, length) in pomodoro_lengths.iter().enumerate() {
+            let mut focus = widget::text_input(fl!("intervals", "focus"), length.focus.to_string());
+            if editable {
+                focus = focus.on_input(move |x| Message::ChangeSetting(SettingMessage::IntervalFocusChanged(index, x)));
+            }
+            let mut delete = widget::button::text(fl!("intervals", "delete"));
+            // the timer always needs at least one interval
+            if pomodoro_lengths.len() > 1 && editable {
+                delete = delete.on_press(Message::ChangeSetting(SettingMessage::DeleteInterval(index)));
+            }
+            // the first entry stands for the sound of the settings
+            let focus_sound = widget::dropdown(&self.override_sound_names, Some(length.focus_end_sound.map_or(0, |x| x + 1)),
+                move |x| Message::ChangeSetting(SettingMessage::IntervalFocusSoundChanged(index, x.checked_sub(1))));
+            let mut row = widget::row::with_capacity(6)
+                .push(widget::text::text(format!("{}.", index + 1)).vertical_alignment(Vertical::Center))
+                .push(focus)
+                .push(focus_sound)
+                .spacing(10);
+            if !self.continuous_focus {
+                let relax_sound = widget::dropdown(&self.override_sound_names, Some(length.relax_end_sound.map_or(0, |x| x + 1)),
+                    move |x| Message::ChangeSetting(SettingMessage::IntervalRelaxSoundChanged(index, x.checked_sub(1))));
+                let mut relax = widget::text_input(fl!("intervals", "relax"), length.relax.to_string());
+                if editable {
+                    relax = relax.on_input(move |x| Message::ChangeSetting(SettingMessage::IntervalRelaxChanged(index, x)));
+                }
+                row = row.push(relax).push(relax_sound);
+            }
+            intervals = intervals.push(row.push(delete));
+            if self.length_error == Some(index) {
+                let warning_color: Color = theme::active().cosmic().warning_color().into();
+                let (min, max) = (*duration_string::LENGTH_RANGE.start(), *duration_string::LENGTH_RANGE.end());
+                intervals = intervals.push(widget::text::caption(fl!("intervals", "out-of-range", min = min, max = max))
+                    .style(theme::Text::Color(warning_color)));
+            }
+        }
+
+        let mut actions = widget::row::with_capacity(3)
+            .push(widget::button::text(fl!("intervals", "add"))
+                .on_press_maybe(editable.then_some(Message::ChangeSetting(SettingMessage::AddInterval))))
+            .spacing(10);
+        if can_undo_delete && editable {
+            actions = actions
+                .push(widget::text::text(fl!("intervals", "deleted")).vertical_alignment(Vertical::Center))
+                .push(widget::button::text(fl!("intervals", "undo"))
+                    .on_press(Message::ChangeSetting(SettingMessage::UndoDeleteInterval)));
+        }
+        intervals.push(actions).into()
+    }
+
     pub fn update(&mut self, message: SettingMessage) {
         match message {
             SettingMessage::EndOfFocusSoundChanged(index) => {
-                self.end_of_focus_sound = index;
+                self.end_of_focus_sound = Some(index);
             }
             SettingMessage::EndOfRelaxSoundChanged(index) => {
-                self.end_of_relax_sound = index;
+                self.end_of_relax_sound = Some(index);
+            }
+            SettingMessage::SearchChanged(search) => {
+                self.search = search;
+            }
+            SettingMessage::SoundFilterChanged(filter) => {
+                self.sound_filter = filter;
+                self.apply_sound_filter();
+            }
+            SettingMessage::AutoStartBreakWhenFocusedChanged(auto_start) => {
+                self.auto_start_break_when_focused = auto_start;
+            }
+            SettingMessage::OvertimeChanged(overtime) => {
+                self.overtime = overtime;
+            }
+            SettingMessage::FocusLockChanged(focus_lock) => {
+                self.focus_lock = focus_lock;
+            }
+            SettingMessage::AdjustKeepsIntervalChanged(adjust_keeps_interval) => {
+                self.adjust_keeps_interval = adjust_keeps_interval;
+            }
+            SettingMessage::PauseOnUnfocusChanged(pause_on_unfocus) => {
+                self.pause_on_unfocus = pause_on_unfocus;
+            }
+            SettingMessage::UnfocusGraceChanged(input) => {
+                if let Ok(secs) = input.parse::<u32>() {
+                    self.unfocus_grace_secs = secs;
+                }
+            }
+            SettingMessage::SkipBehaviorChanged(skip_behavior) => {
+                self.skip_behavior = skip_behavior;
+            }
+            SettingMessage::WarningThresholdChanged(input) => {
+                if let Ok(secs) = input.parse::<u32>() {
+                    self.warning_threshold = secs;
+                }
+            }
+            SettingMessage::MicroReminderIntervalChanged(input) => {
+                if let Ok(secs) = input.parse::<u32>() {
+                    self.micro_reminder_interval_secs = secs;
+                }
+            }
+            SettingMessage::IdlePromptChanged(input) => {
+                if let Ok(secs) = input.parse::<u32>() {
+                    self.idle_prompt_secs = secs;
+                }
+            }
+            SettingMessage::FinalCountdownChanged(final_countdown) => {
+                self.final_countdown = final_countdown;
+            }
+            SettingMessage::ShowClockChanged(show_clock) => {
+                self.show_clock = show_clock;
+            }
+            SettingMessage::ProgressStyleChanged(progress_style) => {
+                self.progress_style = progress_style;
+            }
+            SettingMessage::LayoutChanged(layout) => {
+                self.layout = layout;
+            }
+            SettingMessage::DisplayGranularityChanged(display_granularity) => {
+                self.display_granularity = display_granularity;
+            }
+            SettingMessage::LunchBreakChanged(lunch_break) => {
+                self.lunch_break = lunch_break;
+            }
+            SettingMessage::LunchStartChanged(input) => {
+                if let Ok(time) = NaiveTime::parse_from_str(&input, TIME_FORMAT) {
+                    self.lunch_start = time;
+                }
+                self.lunch_start_input = input;
             }
+            SettingMessage::LunchEndChanged(input) => {
+                if let Ok(time) = NaiveTime::parse_from_str(&input, TIME_FORMAT) {
+                    self.lunch_end = time;
+                }
+                self.lunch_end_input = input;
+            }
+            SettingMessage::LunchBreakLengthChanged(input) => {
+                if let Some(secs) = input.parse::<u32>().ok().and_then(duration_string::clamp_length) {
+                    self.lunch_break_length = secs;
+                }
+            }
+            SettingMessage::QuietHoursChanged(quiet_hours) => {
+                self.quiet_hours = quiet_hours;
+            }
+            SettingMessage::QuietStartChanged(input) => {
+                if let Ok(time) = NaiveTime::parse_from_str(&input, TIME_FORMAT) {
+                    self.quiet_start = time;
+                }
+                self.quiet_start_input = input;
+            }
+            SettingMessage::QuietEndChanged(input) => {
+                if let Ok(time) = NaiveTime::parse_from_str(&input, TIME_FORMAT) {
+                    self.quiet_end = time;
+                }
+                self.quiet_end_input = input;
+            }
+            SettingMessage::QuietPauseChanged(quiet_pause) => {
+                self.quiet_pause = quiet_pause;
+            }
+            SettingMessage::QuietNotificationsChanged(quiet_notifications) => {
+                self.quiet_notifications = quiet_notifications;
+            }
+            SettingMessage::ContinuousFocusChanged(continuous_focus) => {
+                self.continuous_focus = continuous_focus;
+            }
+            SettingMessage::LongBreakTriggerChanged(long_break_trigger) => {
+                self.long_break_trigger = long_break_trigger;
+            }
+            SettingMessage::LongBreakFocusChanged(input) => {
+                if let Ok(mins) = input.parse::<u32>() {
+                    self.long_break_focus_mins = mins;
+                }
+            }
+            SettingMessage::StartPhaseChanged(start_phase) => {
+                self.start_phase = start_phase;
+            }
+            #[cfg(feature = "wayland")]
+            SettingMessage::BreakOverlayChanged(break_overlay) => {
+                self.break_overlay = break_overlay;
+            }
+            #[cfg(feature = "wayland")]
+            SettingMessage::DimOpacityChanged(opacity) => {
+                self.dim_opacity = opacity.clamp(*DIM_OPACITY_RANGE.start(), *DIM_OPACITY_RANGE.end());
+            }
+            SettingMessage::RingStrokeWidthChanged(stroke_width) => {
+                self.ring_stroke_width = stroke_width.clamp(*RING_STROKE_WIDTH_RANGE.start(), *RING_STROKE_WIDTH_RANGE.end());
+            }
+            SettingMessage::RingSizeChanged(size) => {
+                self.ring_size = size.clamp(*RING_SIZE_RANGE.start(), *RING_SIZE_RANGE.end());
+            }
+            SettingMessage::EaseRingChanged(ease_ring) => {
+                self.ease_ring = ease_ring;
+            }
+            SettingMessage::SoundRepeatChanged(sound_repeat) => {
+                self.sound_repeat = sound_repeat.clamp(*SOUND_REPEAT_RANGE.start(), *SOUND_REPEAT_RANGE.end());
+            }
+            SettingMessage::CelebrateCycleChanged(celebrate_cycle) => {
+                self.celebrate_cycle = celebrate_cycle;
+            }
+            SettingMessage::PulseWhenPausedChanged(pulse_when_paused) => {
+                self.pulse_when_paused = pulse_when_paused;
+            }
+            // the intervals belong to the timer and are edited by the app
+            _ => {}
+        }
+    }
+
+    /// The settings kept across launches, see [`SavedSettings`].
+    pub fn saved(&self) -> SavedSettings {
+        SavedSettings {
+            start_phase: self.start_phase,
+            purist: self.purist,
+            quiet_hours: self.quiet_hours,
+            quiet_start: self.quiet_start,
+            quiet_end: self.quiet_end,
+            quiet_pause: self.quiet_pause,
+            quiet_notifications: self.quiet_notifications,
+            end_of_focus_sound: self.get_end_of_focus_sound().to_string(),
+            end_of_relax_sound: self.get_end_of_relax_sound().to_string(),
+            focus_lock: self.focus_lock,
+            sound_repeat: self.sound_repeat,
+            lunch_break: self.lunch_break,
+            lunch_start: self.lunch_start,
+            lunch_end: self.lunch_end,
+            lunch_break_length: self.lunch_break_length,
+        }
+    }
+
+    /// Takes over the settings a timer was saved with.
+    /// The purist mode is turned on by the timer, which also fixes the intervals.
+    pub fn restore(&mut self, saved: &SavedSettings) {
+        self.start_phase = saved.start_phase;
+        self.quiet_hours = saved.quiet_hours;
+        self.quiet_start = saved.quiet_start;
+        self.quiet_start_input = saved.quiet_start.format(TIME_FORMAT).to_string();
+        self.quiet_end = saved.quiet_end;
+        self.quiet_end_input = saved.quiet_end.format(TIME_FORMAT).to_string();
+        self.quiet_pause = saved.quiet_pause;
+        self.quiet_notifications = saved.quiet_notifications;
+        // Sounds are saved by their id, an unknown one keeps the default.
+        if let Some(index) = self.sound_ids.iter().position(|id| *id == saved.end_of_focus_sound) {
+            self.end_of_focus_sound = Some(index);
+        }
+        if let Some(index) = self.sound_ids.iter().position(|id| *id == saved.end_of_relax_sound) {
+            self.end_of_relax_sound = Some(index);
+        }
+        self.focus_lock = saved.focus_lock;
+        self.sound_repeat = saved.sound_repeat.clamp(*SOUND_REPEAT_RANGE.start(), *SOUND_REPEAT_RANGE.end());
+        self.lunch_break = saved.lunch_break;
+        self.lunch_start = saved.lunch_start;
+        self.lunch_start_input = saved.lunch_start.format(TIME_FORMAT).to_string();
+        self.lunch_end = saved.lunch_end;
+        self.lunch_end_input = saved.lunch_end.format(TIME_FORMAT).to_string();
+        if let Some(secs) = duration_string::clamp_length(saved.lunch_break_length) {
+            self.lunch_break_length = secs;
         }
     }
 }
+
+/// The settings of a timer saved with it, so they are the same after a relaunch.
+/// Entries missing in an older config are the ones of a new timer.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct SavedSettings {
+    pub start_phase: StartPhase,
+    pub purist: bool,
+    pub quiet_hours: bool,
+    pub quiet_start: NaiveTime,
+    pub quiet_end: NaiveTime,
+    pub quiet_pause: bool,
+    pub quiet_notifications: bool,
+    /// The freedesktop sound ids, which stay the same if the list of sounds changes.
+    pub end_of_focus_sound: String,
+    pub end_of_relax_sound: String,
+    pub focus_lock: bool,
+    pub sound_repeat: u8,
+    pub lunch_break: bool,
+    pub lunch_start: NaiveTime,
+    pub lunch_end: NaiveTime,
+    pub lunch_break_length: u32,
+}
+
+impl Default for SavedSettings {
+    fn default() -> Self {
+        Settings::new().saved()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) enum SettingMessage {
     EndOfFocusSoundChanged(usize),
     EndOfRelaxSoundChanged(usize),
+    SearchChanged(String),
+    SoundFilterChanged(String),
+    AutoStartBreakWhenFocusedChanged(bool),
+    OvertimeChanged(bool),
+    FocusLockChanged(bool),
+    PuristChanged(bool),
+    AdjustKeepsIntervalChanged(bool),
+    PauseOnUnfocusChanged(bool),
+    UnfocusGraceChanged(String),
+    SkipBehaviorChanged(SkipBehavior),
+    NotificationModeChanged(NotificationMode),
+    WarningThresholdChanged(String),
+    MicroReminderIntervalChanged(String),
+    IdlePromptChanged(String),
+    ShowClockChanged(bool),
+    FinalCountdownChanged(bool),
+    LayoutChanged(Layout),
+    ProgressStyleChanged(ProgressStyle),
+    DisplayGranularityChanged(DisplayGranularity),
+    LunchBreakChanged(bool),
+    LunchStartChanged(String),
+    LunchEndChanged(String),
+    LunchBreakLengthChanged(String),
+    QuietHoursChanged(bool),
+    QuietStartChanged(String),
+    QuietEndChanged(String),
+    QuietPauseChanged(bool),
+    QuietNotificationsChanged(bool),
+    ContinuousFocusChanged(bool),
+    StartPhaseChanged(StartPhase),
+    LongBreakTriggerChanged(LongBreakTrigger),
+    LongBreakFocusChanged(String),
+    #[cfg(feature = "wayland")]
+    BreakOverlayChanged(BreakOverlay),
+    #[cfg(feature = "wayland")]
+    DimOpacityChanged(u32),
+    RingStrokeWidthChanged(u32),
+    RingSizeChanged(u32),
+    EaseRingChanged(bool),
+    CelebrateCycleChanged(bool),
+    SoundRepeatChanged(u8),
+    PulseWhenPausedChanged(bool),
+    FocusIntensityChanged(FocusIntensity),
+    IntervalFocusChanged(usize, String),
+    IntervalRelaxChanged(usize, String),
+    IntervalFocusSoundChanged(usize, Option<usize>),
+    IntervalRelaxSoundChanged(usize, Option<usize>),
+    AddInterval,
+    DeleteInterval(usize),
+    UndoDeleteInterval,
+    DeletedIntervalExpired,
+}
+
+/// What happens to the remaining time of a skipped phase.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum SkipBehavior {
+    Discard,
+    /// Adds the remaining time to the next phase.
+    CarryOver,
+}
+
+impl SkipBehavior {
+    const ALL: [SkipBehavior; 2] = [SkipBehavior::Discard, SkipBehavior::CarryOver];
+
+    fn localized_name(&self) -> String {
+        match self {
+            SkipBehavior::Discard => fl!("skip-behavior", "discard"),
+            SkipBehavior::CarryOver => fl!("skip-behavior", "carry-over"),
+        }
+    }
+}
+
+/// Lengths of a focus intensity preset, in seconds.
+/// The last of its intervals has the long break.
+pub(crate) struct IntensityPreset {
+    pub focus: u32,
+    pub relax: u32,
+    pub long_relax: u32,
+    pub intervals: usize,
+}
+
+pub(crate) const LIGHT_PRESET: IntensityPreset = IntensityPreset { focus: 15 * 60, relax: 5 * 60, long_relax: 15 * 60, intervals: 5 };
+pub(crate) const STANDARD_PRESET: IntensityPreset = IntensityPreset { focus: 25 * 60, relax: 5 * 60, long_relax: 15 * 60, intervals: 5 };
+pub(crate) const DEEP_PRESET: IntensityPreset = IntensityPreset { focus: 50 * 60, relax: 10 * 60, long_relax: 30 * 60, intervals: 5 };
+/// The original technique has the long break after the fourth pomodoro.
+pub(crate) const PURIST_PRESET: IntensityPreset = IntensityPreset { focus: 25 * 60, relax: 5 * 60, long_relax: 15 * 60, intervals: 4 };
+
+impl IntensityPreset {
+    pub fn lengths(&self) -> Vec<PomodoroLength> {
+        (0..self.intervals)
+            .map(|index| {
+                let relax = if index + 1 == self.intervals { self.long_relax } else { self.relax };
+                PomodoroLength::new(self.focus, relax)
+            })
+            .collect()
+    }
+}
+
+/// A quick pick of the interval lengths, instead of editing each interval.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum FocusIntensity {
+    Light,
+    Standard,
+    Deep,
+}
+
+impl FocusIntensity {
+    const ALL: [FocusIntensity; 3] = [FocusIntensity::Light, FocusIntensity::Standard, FocusIntensity::Deep];
+
+    fn localized_name(&self) -> String {
+        match self {
+            FocusIntensity::Light => fl!("focus-intensity", "light"),
+            FocusIntensity::Standard => fl!("focus-intensity", "standard"),
+            FocusIntensity::Deep => fl!("focus-intensity", "deep"),
+        }
+    }
+
+    fn preset(&self) -> &'static IntensityPreset {
+        match self {
+            FocusIntensity::Light => &LIGHT_PRESET,
+            FocusIntensity::Standard => &STANDARD_PRESET,
+            FocusIntensity::Deep => &DEEP_PRESET,
+        }
+    }
+
+    /// The intervals of the preset.
+    pub fn lengths(&self) -> Vec<PomodoroLength> {
+        self.preset().lengths()
+    }
+
+    /// Whether `lengths` are those of the preset, ignoring the sounds of the intervals.
+    fn matches(&self, lengths: &[PomodoroLength]) -> bool {
+        let preset = self.lengths();
+        preset.len() == lengths.len()
+            && preset.iter().zip(lengths).all(|(a, b)| a.focus == b.focus && a.relax == b.relax)
+    }
+}
+
+/// How the end of a phase is announced.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub(crate) enum NotificationMode {
+    SoundOnly,
+    VisualOnly,
+    Both,
+    None,
+}
+
+impl NotificationMode {
+    const ALL: [NotificationMode; 4] = [NotificationMode::Both, NotificationMode::SoundOnly, NotificationMode::VisualOnly, NotificationMode::None];
+
+    fn localized_name(&self) -> String {
+        match self {
+            NotificationMode::SoundOnly => fl!("notification-mode", "sound-only"),
+            NotificationMode::VisualOnly => fl!("notification-mode", "visual-only"),
+            NotificationMode::Both => fl!("notification-mode", "both"),
+            NotificationMode::None => fl!("notification-mode", "none"),
+        }
+    }
+}
+
+/// What makes a break a long one.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum LongBreakTrigger {
+    /// The intervals with the longest break have a long break.
+    Intervals,
+    /// A long break follows once enough time was spent focusing since the last one,
+    /// the other breaks are as long as the shortest break of the intervals.
+    FocusTime,
+}
+
+impl LongBreakTrigger {
+    const ALL: [LongBreakTrigger; 2] = [LongBreakTrigger::Intervals, LongBreakTrigger::FocusTime];
+
+    fn localized_name(&self) -> String {
+        match self {
+            LongBreakTrigger::Intervals => fl!("long-break-trigger", "intervals"),
+            LongBreakTrigger::FocusTime => fl!("long-break-trigger", "focus-time"),
+        }
+    }
+}
+
+/// The phase a timer starts with, e.g. a short planning break at the start of the day.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub(crate) enum StartPhase {
+    Focus,
+    Relax,
+}
+
+impl StartPhase {
+    const ALL: [StartPhase; 2] = [StartPhase::Focus, StartPhase::Relax];
+
+    fn localized_name(&self) -> String {
+        match self {
+            StartPhase::Focus => fl!("start-phase", "focus"),
+            StartPhase::Relax => fl!("start-phase", "relax"),
+        }
+    }
+}
+
+/// How the main view arranges the ring and the texts.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Layout {
+    /// Horizontal if the window is wider than high.
+    Automatic,
+    Vertical,
+    Horizontal,
 }
 
-#[derive(Display, Debug, EnumIter)]
+impl Layout {
+    const ALL: [Layout; 3] = [Layout::Automatic, Layout::Vertical, Layout::Horizontal];
+
+    fn localized_name(&self) -> String {
+        match self {
+            Layout::Automatic => fl!("layout", "automatic"),
+            Layout::Vertical => fl!("layout", "vertical"),
+            Layout::Horizontal => fl!("layout", "horizontal"),
+        }
+    }
+}
+
+/// How the progress of a phase is drawn, all of them from the same percentage.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ProgressStyle {
+    /// A ring around the play button.
+    Ring,
+    /// A line below the play button.
+    Bar,
+    /// The play button filling up like a pie.
+    Pie,
+    /// Only the remaining time, with a plain button to start and pause.
+    None,
+}
+
+impl ProgressStyle {
+    const ALL: [ProgressStyle; 4] = [ProgressStyle::Ring, ProgressStyle::Bar, ProgressStyle::Pie, ProgressStyle::None];
+
+    fn localized_name(&self) -> String {
+        match self {
+            ProgressStyle::Ring => fl!("progress-style", "ring"),
+            ProgressStyle::Bar => fl!("progress-style", "bar"),
+            ProgressStyle::Pie => fl!("progress-style", "pie"),
+            ProgressStyle::None => fl!("progress-style", "none"),
+        }
+    }
+}
+
+/// How precise the remaining time label is.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum DisplayGranularity {
+    Seconds,
+    /// Whole minutes, rounded up, e.g. "25 min".
+    Minutes,
+}
+
+impl DisplayGranularity {
+    const ALL: [DisplayGranularity; 2] = [DisplayGranularity::Seconds, DisplayGranularity::Minutes];
+
+    fn localized_name(&self) -> String {
+        match self {
+            DisplayGranularity::Seconds => fl!("display-granularity", "seconds"),
+            DisplayGranularity::Minutes => fl!("display-granularity", "minutes"),
+        }
+    }
+}
+
+/// Whether a break covers the screen, and if it can be skipped from there.
+#[cfg(feature = "wayland")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum BreakOverlay {
+    Off,
+    /// The overlay has a skip button.
+    Flexible,
+    /// The overlay stays until the break is over.
+    Strict,
+    /// Dims the screen, which stays usable, see `dim_opacity`.
+    Dim,
+}
+
+#[cfg(feature = "wayland")]
+impl BreakOverlay {
+    const ALL: [BreakOverlay; 4] = [BreakOverlay::Off, BreakOverlay::Dim, BreakOverlay::Flexible, BreakOverlay::Strict];
+
+    fn localized_name(&self) -> String {
+        match self {
+            BreakOverlay::Off => fl!("break-overlay", "off"),
+            BreakOverlay::Flexible => fl!("break-overlay", "flexible"),
+            BreakOverlay::Strict => fl!("break-overlay", "strict"),
+            BreakOverlay::Dim => fl!("break-overlay", "dim"),
+        }
+    }
+}
+
+/// Whether `time` is within the window from `start` until `end`, which may span midnight.
+fn is_within(start: NaiveTime, end: NaiveTime, time: NaiveTime) -> bool {
+    if start <= end {
+        start <= time && time < end
+    } else {
+        start <= time || time < end
+    }
+}
+
+/// Turns a `SoundName` like `AlarmClockElapsed` into its freedesktop sound id `alarm-clock-elapsed`.
+fn to_sound_id(name: &str) -> String {
+    let mut sound_id = String::with_capacity(name.len() + 4);
+    for (index, character) in name.chars().enumerate() {
+        if character.is_uppercase() && index > 0 {
+            sound_id.push('-');
+        }
+        sound_id.extend(character.to_lowercase());
+    }
+    sound_id
+}
+
+#[derive(Display, Debug, EnumIter, Eq, PartialEq)]
 enum SoundName {
     MessageNewInstant,
     MessageNewEmail,
@@ -112,4 +1154,111 @@ enum SoundName {
     AlarmClockElapsed,
     WindowAttentionActive,
     WindowAttentionInactive,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn window_within_a_day() {
+        let (start, end) = (time(12, 0), time(13, 0));
+        assert!(is_within(start, end, time(12, 0)));
+        assert!(is_within(start, end, time(12, 59)));
+        // the end is the first minute outside of it
+        assert!(!is_within(start, end, time(13, 0)));
+        assert!(!is_within(start, end, time(11, 59)));
+    }
+
+    #[test]
+    fn window_spanning_midnight() {
+        let (start, end) = (time(22, 0), time(7, 0));
+        assert!(is_within(start, end, time(23, 30)));
+        assert!(is_within(start, end, time(0, 0)));
+        assert!(is_within(start, end, time(6, 59)));
+        assert!(!is_within(start, end, time(7, 0)));
+        assert!(!is_within(start, end, time(12, 0)));
+    }
+
+    #[test]
+    fn windows_only_apply_when_turned_on() {
+        let mut settings = Settings::new();
+        assert!(!settings.is_lunch_time(time(12, 30)));
+        assert!(!settings.is_quiet_time(time(23, 0)));
+        settings.lunch_break = true;
+        settings.quiet_hours = true;
+        assert!(settings.is_lunch_time(time(12, 30)));
+        assert!(settings.is_quiet_time(time(23, 0)));
+        assert!(!settings.is_quiet_time(time(12, 30)));
+    }
+
+    #[test]
+    fn quiet_hours_are_restored() {
+        let mut settings = Settings::new();
+        settings.update(SettingMessage::QuietHoursChanged(true));
+        settings.update(SettingMessage::QuietStartChanged("23:30".to_string()));
+        settings.update(SettingMessage::QuietEndChanged("06:00".to_string()));
+        settings.update(SettingMessage::QuietNotificationsChanged(false));
+        let mut restored = Settings::new();
+        restored.restore(&settings.saved());
+        assert!(restored.is_quiet_time(time(0, 30)));
+        assert!(!restored.is_quiet_time(time(23, 0)));
+        assert_eq!(restored.quiet_start_input, "23:30");
+        assert!(!restored.quiet_notifications);
+    }
+
+    #[test]
+    fn sounds_are_restored_by_their_id() {
+        let mut settings = Settings::new();
+        let complete = SoundName::iter().position(|x| x == SoundName::CompleteDownload).unwrap();
+        settings.update(SettingMessage::EndOfFocusSoundChanged(complete));
+        let mut restored = Settings::new();
+        restored.restore(&settings.saved());
+        assert_eq!(restored.get_end_of_focus_sound(), settings.get_end_of_focus_sound());
+        assert_eq!(restored.get_end_of_relax_sound(), Settings::new().get_end_of_relax_sound());
+
+        let mut unknown = settings.saved();
+        unknown.end_of_focus_sound = "no-such-sound".to_string();
+        restored.restore(&unknown);
+        assert_eq!(restored.get_end_of_focus_sound(), settings.get_end_of_focus_sound());
+    }
+
+    #[test]
+    fn focus_lock_is_restored() {
+        let mut settings = Settings::new();
+        settings.update(SettingMessage::FocusLockChanged(true));
+        let mut restored = Settings::new();
+        restored.restore(&settings.saved());
+        assert!(restored.focus_lock);
+    }
+
+    #[test]
+    fn sound_repeat_is_restored_within_its_range() {
+        let mut settings = Settings::new();
+        settings.update(SettingMessage::SoundRepeatChanged(3));
+        let mut restored = Settings::new();
+        restored.restore(&settings.saved());
+        assert_eq!(restored.sound_repeat, 3);
+
+        let mut edited = settings.saved();
+        edited.sound_repeat = 0;
+        restored.restore(&edited);
+        assert_eq!(restored.sound_repeat, *SOUND_REPEAT_RANGE.start());
+    }
+
+    #[test]
+    fn lunch_window_is_restored() {
+        let mut settings = Settings::new();
+        settings.update(SettingMessage::LunchBreakChanged(true));
+        settings.update(SettingMessage::LunchStartChanged("11:30".to_string()));
+        settings.update(SettingMessage::LunchBreakLengthChanged("2700".to_string()));
+        let mut restored = Settings::new();
+        restored.restore(&settings.saved());
+        assert!(restored.is_lunch_time(time(11, 45)));
+        assert_eq!(restored.lunch_start_input, "11:30");
+        assert_eq!(restored.lunch_break_length, 2700);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::app::Message;
+use crate::core::config::Config;
+use crate::core::duration_extension::TimeDurationExt;
+use crate::core::session_history::Distraction;
+use crate::core::summary_card::SummaryCard;
+use crate::core::{localization, statistics};
+use crate::fl;
+use crate::views::settings::TIME_FORMAT;
+use chrono::NaiveDate;
+use cosmic::iced::alignment::Vertical;
+use cosmic::{widget, Element};
+use std::time::Duration;
+
+pub fn get_statistics_view<'a>(config: &'a Config, summary_time_input: &'a str, today: NaiveDate) -> Element<'a, Message> {
+    let title = widget::text::title3(fl!("statistics"));
+    let completed_today = config.daily_completed.get(&today).copied().unwrap_or(0);
+    let interruptions_today = config.daily_interruptions.get(&today).copied().unwrap_or(0);
+    let overtime_today = Duration::from_secs(config.daily_overtime_secs.get(&today).copied().unwrap_or(0) as u64);
+    let current_streak = statistics::current_streak(&config.daily_completed, config.daily_goal, today);
+
+    let daily_goal = widget::text_input("", config.daily_goal.to_string())
+        .on_input(Message::DailyGoalChanged);
+    let count_partial = widget::toggler(None, config.stats_count_partial, Message::StatsCountPartialChanged);
+    let count_after_break = widget::toggler(None, config.count_after_break, Message::CountAfterBreakChanged);
+    let summary_at_time = widget::toggler(None, config.summary_at.is_some(), Message::SummaryAtTimeChanged);
+    let summary_on_goal = widget::toggler(None, config.summary_on_goal, Message::SummaryOnGoalChanged);
+
+    let has_data = SummaryCard::new(config, today).has_data();
+    let mut share = widget::row::with_capacity(2)
+        .push(widget::button::text(fl!("summary-card"))
+            .on_press_maybe(has_data.then_some(Message::ExportSummaryCard)))
+        .spacing(10);
+    if !has_data {
+        share = share.push(widget::text::caption(fl!("summary-card", "empty")).vertical_alignment(Vertical::Center));
+    }
+
+    let mut summary = widget::column()
+        .push(widget::text::title4(fl!("summary")))
+        .push(widget::row::with_capacity(2)
+            .push(widget::text::text(fl!("summary", "at-time")).vertical_alignment(Vertical::Center))
+            .push(summary_at_time)
+            .spacing(10)
+        )
+        .spacing(10);
+    if config.summary_at.is_some() {
+        let summary_time = widget::text_input(TIME_FORMAT, summary_time_input)
+            .on_input(Message::SummaryTimeChanged);
+        summary = summary.push(widget::row::with_capacity(2)
+            .push(widget::text::text(fl!("summary", "time")).vertical_alignment(Vertical::Center))
+            .push(summary_time)
+            .spacing(10)
+        );
+    }
+    summary = summary.push(widget::row::with_capacity(2)
+        .push(widget::text::text(fl!("summary", "on-goal")).vertical_alignment(Vertical::Center))
+        .push(summary_on_goal)
+        .spacing(10)
+    );
+
+    widget::column()
+        .push(title)
+        .push(widget::text::text(fl!("statistics", "completed-today", count = completed_today)))
+        .push(widget::text::text(fl!("statistics", "interruptions-today", count = interruptions_today)))
+        .push(widget::text::text(fl!("statistics", "overtime-today", time = localization::localize_digits(&overtime_today.to_hours_string()))))
+        .push(widget::row::with_capacity(2)
+            .push(widget::text::text(fl!("statistics", "daily-goal")).vertical_alignment(Vertical::Center))
+            .push(daily_goal)
+            .spacing(10)
+        )
+        .push(widget::text::text(fl!("statistics", "current-streak", days = current_streak)))
+        .push(widget::text::text(fl!("statistics", "longest-streak", days = config.longest_streak)))
+        .push(share)
+        .push(widget::row::with_capacity(2)
+            .push(widget::text::text(fl!("statistics", "count-partial")).vertical_alignment(Vertical::Center))
+            .push(count_partial)
+            .spacing(10)
+        )
+        .push(widget::row::with_capacity(2)
+            .push(widget::text::text(fl!("statistics", "count-after-break")).vertical_alignment(Vertical::Center))
+            .push(count_after_break)
+            .spacing(10)
+        )
+        .push(summary)
+        .spacing(10)
+        .into()
+}
+
+/// The interruptions of `today` with their times and notes, the latest first.
+pub fn get_distraction_log_view<'a, 'b>(distractions: impl Iterator<Item=&'b Distraction>, today: NaiveDate) -> Element<'a, Message> {
+    let mut distractions = distractions
+        .filter(|distraction| distraction.at.date_naive() == today)
+        .collect::<Vec<_>>();
+    distractions.sort_by_key(|distraction| std::cmp::Reverse(distraction.at));
+    let mut root = widget::column()
+        .push(widget::text::title4(fl!("distraction-log")))
+        .spacing(10);
+    if distractions.is_empty() {
+        root = root.push(widget::text::caption(fl!("distraction-log", "empty")));
+    }
+    for distraction in distractions {
+        let time = localization::format_time_of_day(distraction.at);
+        let note = if distraction.note.is_empty() { fl!("distraction-log", "no-note") } else { distraction.note.clone() };
+        root = root.push(widget::text::text(fl!("distraction-log", "entry", time = time, note = note)));
+    }
+    root.into()
+}
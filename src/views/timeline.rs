@@ -0,0 +1,59 @@
+use crate::app::Message;
+use crate::core::pomodoro_timer::{PomodoroLength, PomodoroPhase};
+use crate::fl;
+use cosmic::iced::widget::container;
+use cosmic::iced::{Background, Border, Color, Length};
+use cosmic::{theme, widget, Element};
+
+/// Height of the segments of the timeline.
+const SEGMENT_HEIGHT: f32 = 16.0;
+
+/// The color standing for a phase, the accent for focusing and the success color for breaks.
+pub fn phase_color(phase: PomodoroPhase) -> Color {
+    let cosmic = theme::active().cosmic().clone();
+    match phase {
+        PomodoroPhase::BeforeFocus | PomodoroPhase::Focus => cosmic.accent_color().into(),
+        PomodoroPhase::BeforeRelax | PomodoroPhase::Relax => cosmic.success_color().into(),
+    }
+}
+
+/// The planned cycle as a strip of focus and break segments, as wide as they are long.
+/// The segment of the current or upcoming phase is highlighted.
+pub fn get_timeline_view<'a>(pomodoro_lengths: &[PomodoroLength], position: usize, phase: PomodoroPhase, continuous_focus: bool) -> Element<'a, Message> {
+    let focus_color = phase_color(PomodoroPhase::Focus);
+    let relax_color = phase_color(PomodoroPhase::Relax);
+    let current_is_focus = matches!(phase, PomodoroPhase::BeforeFocus | PomodoroPhase::Focus);
+
+    let mut segments = Vec::with_capacity(pomodoro_lengths.len() * 2);
+    for (index, length) in pomodoro_lengths.iter().enumerate() {
+        segments.push(segment(length.focus, focus_color, index == position && current_is_focus));
+        if !continuous_focus {
+            segments.push(segment(length.relax, relax_color, index == position && !current_is_focus));
+        }
+    }
+    widget::column()
+        .push(widget::text::title4(fl!("timeline")))
+        .push(widget::row::with_children(segments).spacing(2))
+        .spacing(10)
+        .into()
+}
+
+fn segment<'a>(secs: u32, mut color: Color, is_current: bool) -> Element<'a, Message> {
+    // the other segments stay visible, but in the background
+    if !is_current {
+        color.a = 0.35;
+    }
+    // a phase of no length still gets a sliver
+    let portion = secs.clamp(1, u16::MAX as u32) as u16;
+    widget::container(widget::Space::with_height(Length::Fixed(SEGMENT_HEIGHT)))
+        .width(Length::FillPortion(portion))
+        .style(theme::Container::custom(move |_| container::Appearance {
+            background: Some(Background::Color(color)),
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }))
+        .into()
+}